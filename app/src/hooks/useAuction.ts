@@ -7,20 +7,28 @@ import {
   SystemProgram,
   SYSVAR_INSTRUCTIONS_PUBKEY,
   Transaction,
+  TransactionInstruction,
   VersionedTransaction,
   TransactionMessage,
 } from "@solana/web3.js";
 import { BN } from "@coral-xyz/anchor";
 import { useProgram } from "./useProgram";
 import {
+  getAllowlistPDA,
   getAuctionPDA,
   getBidPDA,
+  getConfigPDA,
+  getSellerProfilePDA,
   getVaultPDA,
   getCommentPDA,
+  getCommentRecordPDA,
+  getSellerHandles,
   INCO_LIGHTNING_PROGRAM_ID,
   AuctionAccount,
   BidAccount,
   CommentAccount,
+  CreateAuctionParams,
+  DEFAULT_CREATE_AUCTION_PARAMS,
 } from "@/lib/program";
 import { decrypt } from "@inco/solana-sdk/attested-decrypt";
 import { handleToBuffer, plaintextToBuffer, hexToBuffer } from "@inco/solana-sdk/utils";
@@ -149,7 +157,8 @@ export function useAuction() {
       description: string,
       category: string,
       imageUrl: string,
-      tags: string[]
+      tags: string[],
+      params: CreateAuctionParams = DEFAULT_CREATE_AUCTION_PARAMS
     ): Promise<string | null> => {
       if (!program || !publicKey) {
         setError("Wallet not connected");
@@ -163,10 +172,16 @@ export function useAuction() {
         const [auctionPDA] = getAuctionPDA(auctionId);
         const [vaultPDA] = getVaultPDA(auctionPDA);
 
+        const [configPDA] = getConfigPDA();
+        // Created by the program with the seller's first auction
+        const [sellerProfilePDA] = getSellerProfilePDA(publicKey);
+
         const tx = await program.methods
-          .createAuction(auctionId, minimumBid, endTime, title, description, category, imageUrl, tags)
+          .createAuction(auctionId, minimumBid, endTime, title, description, category, imageUrl, tags, params)
           .accounts({
             authority: publicKey,
+            config: configPDA,
+            sellerProfile: sellerProfilePDA,
             auction: auctionPDA,
             vault: vaultPDA,
             systemProgram: SystemProgram.programId,
@@ -187,12 +202,54 @@ export function useAuction() {
     [program, publicKey]
   );
 
+  // Get a handle logged by the program from simulation logs. The simulation
+  // fails without allowance accounts, but only after the handle is logged.
+  const getHandleFromSimulation = useCallback(
+    async (
+      ix: TransactionInstruction,
+      prefix: string = "Result handle:"
+    ): Promise<bigint | null> => {
+      if (!publicKey) return null;
+
+      try {
+        const { blockhash } = await connection.getLatestBlockhash();
+
+        // Create a VersionedTransaction for simulation
+        const messageV0 = new TransactionMessage({
+          payerKey: publicKey,
+          recentBlockhash: blockhash,
+          instructions: [ix],
+        }).compileToV0Message();
+
+        const versionedTx = new VersionedTransaction(messageV0);
+
+        // Simulate without signatures
+        const sim = await connection.simulateTransaction(versionedTx, {
+          sigVerify: false,
+        });
+
+        for (const log of sim.value.logs || []) {
+          if (log.includes(prefix)) {
+            const match = log.match(/(\d+)/);
+            if (match) return BigInt(match[1]);
+          }
+        }
+        return null;
+      } catch (err) {
+        console.error("Simulation error:", err);
+        return null;
+      }
+    },
+    [publicKey, connection]
+  );
+
   // Place a bid with encrypted bid amount
   const placeBid = useCallback(
     async (
       auctionPDA: PublicKey,
       bidAmount: number, // in SOL
-      depositAmount: number // in SOL (should be >= bidAmount)
+      depositAmount: number, // in SOL (should be >= bidAmount)
+      merkleProof: number[][] = [] // membership proof for Merkle-root auctions
     ): Promise<string | null> => {
       if (!program || !publicKey) {
         setError("Wallet not connected");
@@ -209,17 +266,48 @@ export function useAuction() {
 
         const [bidPDA] = getBidPDA(auctionPDA, publicKey);
         const [vaultPDA] = getVaultPDA(auctionPDA);
+        const [configPDA] = getConfigPDA();
+
+        // Allowlist auctions check the bidder against their allowlist account
+        const auction = (await (program.account as any).auction.fetch(
+          auctionPDA
+        )) as AuctionAccount;
+        const allowlist = auction.access.allowlist
+          ? getAllowlistPDA(auctionPDA)[0]
+          : null;
+
+        const builder = () =>
+          program.methods
+            .placeBid(encryptedBidBuffer, new BN(depositAmount * 1e9), merkleProof)
+            .accounts({
+              bidder: publicKey,
+              auction: auctionPDA,
+              config: configPDA,
+              allowlist,
+              gateTokenAccount: null,
+              gateMetadata: null,
+              bid: bidPDA,
+              vault: vaultPDA,
+              systemProgram: SystemProgram.programId,
+              incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+            });
+
+        // Simulate to learn the bid handle, then grant ourselves access to it
+        const bidHandle = await getHandleFromSimulation(
+          await builder().instruction(),
+          "Bid handle:"
+        );
+        if (!bidHandle) {
+          setError("Could not get bid handle from simulation");
+          return null;
+        }
+        const [allowancePda] = deriveAllowancePda(bidHandle, publicKey);
 
-        const tx = await program.methods
-          .placeBid(encryptedBidBuffer, new BN(depositAmount * 1e9))
-          .accounts({
-            bidder: publicKey,
-            auction: auctionPDA,
-            bid: bidPDA,
-            vault: vaultPDA,
-            systemProgram: SystemProgram.programId,
-            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
-          })
+        const tx = await builder()
+          .remainingAccounts([
+            { pubkey: allowancePda, isSigner: false, isWritable: true },
+            { pubkey: publicKey, isSigner: false, isWritable: false },
+          ])
           .rpc();
 
         console.log("Bid placed:", tx);
@@ -233,7 +321,7 @@ export function useAuction() {
         setLoading(false);
       }
     },
-    [program, publicKey]
+    [program, publicKey, getHandleFromSimulation]
   );
 
   // Close auction (authority only)
@@ -248,12 +336,28 @@ export function useAuction() {
       setError(null);
 
       try {
+        // Grant ourselves the seller-only handles (highest bid, clearing price, histogram)
+        const auction = (await (program.account as any).auction.fetch(
+          auctionPDA
+        )) as AuctionAccount;
+        const allowances = getSellerHandles(auction).flatMap((handle) => [
+          {
+            pubkey: deriveAllowancePda(handle, publicKey)[0],
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: publicKey, isSigner: false, isWritable: false },
+        ]);
+
         const tx = await program.methods
           .closeAuction()
           .accounts({
             authority: publicKey,
             auction: auctionPDA,
+            systemProgram: SystemProgram.programId,
+            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
           })
+          .remainingAccounts(allowances)
           .rpc();
 
         console.log("Auction closed:", tx);
@@ -270,60 +374,6 @@ export function useAuction() {
     [program, publicKey]
   );
 
-  // Get result handle from simulation logs
-  const getHandleFromSimulation = useCallback(
-    async (
-      auctionPDA: PublicKey,
-      prefix: string = "Result handle:"
-    ): Promise<bigint | null> => {
-      if (!program || !publicKey) return null;
-
-      try {
-        const [bidPDA] = getBidPDA(auctionPDA, publicKey);
-
-        // Build the instruction
-        const ix = await program.methods
-          .checkWin()
-          .accounts({
-            bidder: publicKey,
-            auction: auctionPDA,
-            bid: bidPDA,
-            systemProgram: SystemProgram.programId,
-            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
-          })
-          .instruction();
-
-        const { blockhash } = await connection.getLatestBlockhash();
-
-        // Create a VersionedTransaction for simulation
-        const messageV0 = new TransactionMessage({
-          payerKey: publicKey,
-          recentBlockhash: blockhash,
-          instructions: [ix],
-        }).compileToV0Message();
-
-        const versionedTx = new VersionedTransaction(messageV0);
-
-        // Simulate without signatures
-        const sim = await connection.simulateTransaction(versionedTx, {
-          sigVerify: false,
-        });
-
-        for (const log of sim.value.logs || []) {
-          if (log.includes(prefix)) {
-            const match = log.match(/(\d+)/);
-            if (match) return BigInt(match[1]);
-          }
-        }
-        return null;
-      } catch (err) {
-        console.error("Simulation error:", err);
-        return null;
-      }
-    },
-    [program, publicKey, connection]
-  );
-
   // Check if bid is winner - with proper allowance setup
   const checkWin = useCallback(
     async (
@@ -341,7 +391,17 @@ export function useAuction() {
         const [bidPDA] = getBidPDA(auctionPDA, publicKey);
 
         // Step 1: Simulate to get the result handle
-        const resultHandle = await getHandleFromSimulation(auctionPDA);
+        const simIx = await program.methods
+          .checkWin()
+          .accounts({
+            bidder: publicKey,
+            auction: auctionPDA,
+            bid: bidPDA,
+            systemProgram: SystemProgram.programId,
+            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          })
+          .instruction();
+        const resultHandle = await getHandleFromSimulation(simIx);
 
         if (!resultHandle) {
           setError("Could not get result handle from simulation");
@@ -524,7 +584,11 @@ export function useAuction() {
 
   // Add a comment to an auction
   const addComment = useCallback(
-    async (auctionPDA: PublicKey, commentText: string): Promise<string | null> => {
+    async (
+      auctionPDA: PublicKey,
+      commentText: string,
+      parent: PublicKey | null = null
+    ): Promise<string | null> => {
       if (!program || !wallet.publicKey) {
         setError("Wallet not connected");
         return null;
@@ -534,16 +598,29 @@ export function useAuction() {
       setError(null);
 
       try {
-        const commentId = new BN(Date.now());
-        const [commentPDA] = getCommentPDA(auctionPDA, commentId);
+        // Comments are numbered by the auction's comment_count
+        const auction = (await (program.account as any).auction.fetch(
+          auctionPDA
+        )) as AuctionAccount;
+        const [commentPDA] = getCommentPDA(auctionPDA, new BN(auction.commentCount));
+        const bid =
+          auction.commentPolicy.biddersOnly && !auction.authority.equals(wallet.publicKey)
+            ? getBidPDA(auctionPDA, wallet.publicKey)[0]
+            : null;
+        const commentRecord = new BN(auction.commentInterval).gtn(0)
+          ? getCommentRecordPDA(auctionPDA, wallet.publicKey)[0]
+          : null;
 
         // Anchor converts snake_case to camelCase: add_comment -> addComment
         const tx = await program.methods
-          .addComment(commentId, commentText)
+          .addComment(commentText)
           .accounts({
             commenter: wallet.publicKey,
             auction: auctionPDA,
             comment: commentPDA,
+            parent,
+            bid,
+            commentRecord,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
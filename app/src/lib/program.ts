@@ -28,6 +28,17 @@ export function getProgram(
 }
 
 // PDA derivation functions for blind auction
+export function getConfigPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], PROGRAM_ID);
+}
+
+export function getSellerProfilePDA(seller: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("seller"), seller.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
 export function getAuctionPDA(auctionId: BN): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("auction"), auctionId.toArrayLike(Buffer, "le", 8)],
@@ -52,6 +63,13 @@ export function getVaultPDA(auction: PublicKey): [PublicKey, number] {
   );
 }
 
+export function getAllowlistPDA(auction: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("allowlist"), auction.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
 export function getCommentPDA(
   auction: PublicKey,
   commentId: BN
@@ -62,6 +80,44 @@ export function getCommentPDA(
   );
 }
 
+export function getCommentRecordPDA(
+  auction: PublicKey,
+  commenter: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("comment_record"), auction.toBuffer(), commenter.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+// Optional auction settings passed to create_auction
+export interface CreateAuctionParams {
+  access: { open?: object; allowlist?: object; merkleRoot?: [number[]] };
+  tokenGate: { kind: { mint?: object; collection?: object }; key: PublicKey; minAmount: BN } | null;
+  units: number;
+  pricing: { payAsBid?: object; uniform?: object };
+  histogramBuckets: number;
+  publicResult: boolean;
+  deliveryEscrow: boolean;
+  arbiter: PublicKey | null;
+  commentPolicy: { open?: object; biddersOnly?: object; disabled?: object; lockedAfterClose?: object };
+  commentInterval: BN;
+}
+
+// Open single-unit pay-as-bid auction with every extra off
+export const DEFAULT_CREATE_AUCTION_PARAMS: CreateAuctionParams = {
+  access: { open: {} },
+  tokenGate: null,
+  units: 1,
+  pricing: { payAsBid: {} },
+  histogramBuckets: 0,
+  publicResult: false,
+  deliveryEscrow: false,
+  arbiter: null,
+  commentPolicy: { open: {} },
+  commentInterval: new BN(0),
+};
+
 // Convert u128 handle to Buffer
 export function handleToBuffer(handle: BN | bigint): Buffer {
   const bn = typeof handle === "bigint" ? new BN(handle.toString()) : handle;
@@ -80,6 +136,15 @@ export interface AuctionAccount {
   highestBidHandle: BN;
   winnerDetermined: boolean;
   bump: number;
+  access: { open?: object; allowlist?: object; merkleRoot?: [number[]] };
+  units: number;
+  pricing: { payAsBid?: object; uniform?: object };
+  topBidHandles: BN[];
+  bidHistogram: BN[];
+  publicResult: boolean;
+  commentCount: BN;
+  commentPolicy: { open?: object; biddersOnly?: object; disabled?: object; lockedAfterClose?: object };
+  commentInterval: BN;
   title: string;
   description: string;
   category: string;
@@ -87,6 +152,20 @@ export interface AuctionAccount {
   tags: string[];
 }
 
+// Handles the seller is granted at close, in the order close_auction expects
+// their allowance accounts: clearing price (uniform pricing), highest bid, histogram
+export function getSellerHandles(auction: AuctionAccount): bigint[] {
+  const handles: bigint[] = [];
+  if (auction.pricing.uniform && auction.topBidHandles.length > auction.units) {
+    handles.push(BigInt(auction.topBidHandles[auction.units].toString()));
+  }
+  handles.push(BigInt(auction.highestBidHandle.toString()));
+  for (const handle of auction.bidHistogram) {
+    handles.push(BigInt(handle.toString()));
+  }
+  return handles;
+}
+
 // Bid account type
 export interface BidAccount {
   auction: PublicKey;
@@ -104,8 +183,14 @@ export interface BidAccount {
 export interface CommentAccount {
   auction: PublicKey;
   commenter: PublicKey;
+  commentId: BN;
   comment: string;
   timestamp: BN;
+  editedAt: BN;
+  flagCount: number;
+  parent: PublicKey | null;
+  replyCount: number;
+  answer: PublicKey | null;
   bump: number;
 }
 
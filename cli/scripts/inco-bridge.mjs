@@ -0,0 +1,67 @@
+// Thin bridge between the Rust CLI and the Inco JS SDK.
+//
+// Usage:
+//   node inco-bridge.mjs encrypt <value>
+//   node inco-bridge.mjs decrypt <handle> <keypair.json>
+//
+// Every command prints a single JSON object on stdout.
+import { readFileSync } from "fs";
+import { Keypair } from "@solana/web3.js";
+import nacl from "tweetnacl";
+import { encryptValue } from "@inco/solana-sdk/encryption";
+import { decrypt } from "@inco/solana-sdk/attested-decrypt";
+import { handleToBuffer, plaintextToBuffer } from "@inco/solana-sdk/utils";
+
+function loadKeypair(path) {
+  const secret = Uint8Array.from(JSON.parse(readFileSync(path, "utf8")));
+  return Keypair.fromSecretKey(secret);
+}
+
+async function encrypt(value) {
+  const ciphertext = await encryptValue(BigInt(value));
+  return { ciphertext: ciphertext.replace(/^0x/, "") };
+}
+
+async function decryptHandle(handle, keypairPath) {
+  const keypair = loadKeypair(keypairPath);
+  const result = await decrypt([handle], {
+    address: keypair.publicKey,
+    signMessage: async (msg) => nacl.sign.detached(msg, keypair.secretKey),
+  });
+  const plaintext = result.plaintexts[0];
+  return {
+    plaintext,
+    handleBytes: Buffer.from(handleToBuffer(handle)).toString("hex"),
+    plaintextBytes: Buffer.from(plaintextToBuffer(plaintext)).toString("hex"),
+    ed25519Instructions: result.ed25519Instructions.map((ix) => ({
+      programId: ix.programId.toBase58(),
+      keys: ix.keys.map((k) => ({
+        pubkey: k.pubkey.toBase58(),
+        isSigner: k.isSigner,
+        isWritable: k.isWritable,
+      })),
+      data: Buffer.from(ix.data).toString("hex"),
+    })),
+  };
+}
+
+async function main() {
+  const [command, ...args] = process.argv.slice(2);
+  let output;
+  switch (command) {
+    case "encrypt":
+      output = await encrypt(args[0]);
+      break;
+    case "decrypt":
+      output = await decryptHandle(args[0], args[1]);
+      break;
+    default:
+      throw new Error(`unknown command: ${command}`);
+  }
+  process.stdout.write(JSON.stringify(output));
+}
+
+main().catch((err) => {
+  console.error(err.message ?? err);
+  process.exit(1);
+});
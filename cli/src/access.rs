@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{pubkey::Pubkey, system_program},
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    metadata::mpl_token_metadata::accounts::Metadata,
+    token_interface::TokenAccount,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use blind_auction::{
+    access::{merkle_proof, merkle_root},
+    accounts, instruction,
+    state::{Auction, BidderAccess, TokenGate, TokenGateKind},
+};
+
+use crate::{build_ix, AuctionProgram};
+
+/// Allowlist entries sent per transaction
+const ALLOWLIST_BATCH: usize = 20;
+
+/// Read one base58 pubkey per line; blank lines and `#` comments are skipped
+pub fn read_members(path: &Path) -> Result<Vec<Pubkey>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| Pubkey::from_str(line).map_err(|e| anyhow!("invalid pubkey {:?}: {}", line, e)))
+        .collect()
+}
+
+pub fn merkle_access(members: &[Pubkey]) -> Result<BidderAccess> {
+    if members.is_empty() {
+        bail!("members file is empty");
+    }
+    Ok(BidderAccess::MerkleRoot(merkle_root(members)))
+}
+
+/// Create the allowlist account of `auction`, filling it in batches
+pub fn create_allowlist(program: &AuctionProgram, auction: Pubkey, bidders: &[Pubkey]) -> Result<()> {
+    let authority = program.payer();
+    let allowlist = allowlist_pda(&auction);
+    for (i, batch) in bidders.chunks(ALLOWLIST_BATCH).enumerate() {
+        let ix = if i == 0 {
+            build_ix(
+                accounts::CreateAllowlist { authority, auction, allowlist, system_program: system_program::ID },
+                instruction::CreateAllowlist { bidders: batch.to_vec() },
+                vec![],
+            )
+        } else {
+            build_ix(
+                accounts::AddToAllowlist { authority, auction, allowlist },
+                instruction::AddToAllowlist { bidders: batch.to_vec() },
+                vec![],
+            )
+        };
+        program.request().instruction(ix).send()?;
+    }
+    println!("   Allowlist: {} bidder(s) at {}", bidders.len(), allowlist);
+    Ok(())
+}
+
+/// Allowlist account and Merkle proof `bidder` must present to bid on `auction`
+pub fn bid_credentials(
+    auction_key: &Pubkey,
+    auction: &Auction,
+    bidder: &Pubkey,
+    members_file: Option<&Path>,
+) -> Result<(Option<Pubkey>, Vec<[u8; 32]>)> {
+    match auction.access {
+        BidderAccess::Open => Ok((None, vec![])),
+        BidderAccess::Allowlist => Ok((Some(allowlist_pda(auction_key)), vec![])),
+        BidderAccess::MerkleRoot(root) => {
+            let path = members_file.ok_or_else(|| anyhow!("invite-only auction, pass --members-file"))?;
+            let members = read_members(path)?;
+            if merkle_root(&members) != root {
+                bail!("{} does not match the auction's Merkle root", path.display());
+            }
+            let index = members
+                .iter()
+                .position(|m| m == bidder)
+                .ok_or_else(|| anyhow!("{} is not on the auction's allowlist", bidder))?;
+            Ok((None, merkle_proof(&members, index)))
+        }
+    }
+}
+
+/// Token account (and NFT metadata for collection gates) `bidder` presents to a
+/// token-gated auction. Without `token_account`, mint gates use the bidder's
+/// associated token account.
+pub fn gate_accounts(
+    program: &AuctionProgram,
+    gate: Option<&TokenGate>,
+    bidder: &Pubkey,
+    token_account: Option<Pubkey>,
+) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+    let Some(gate) = gate else {
+        return Ok((None, None));
+    };
+    let token_account = match (token_account, gate.kind) {
+        (Some(address), _) => address,
+        (None, TokenGateKind::Mint) => find_associated_account(program, bidder, &gate.key)?,
+        (None, TokenGateKind::Collection) => {
+            bail!("collection-gated auction, pass --gate-token-account with the NFT you hold")
+        }
+    };
+    let metadata = match gate.kind {
+        TokenGateKind::Mint => None,
+        TokenGateKind::Collection => {
+            let data = program.rpc().get_account_data(&token_account)?;
+            let held = TokenAccount::try_deserialize(&mut data.as_slice())?;
+            Some(Metadata::find_pda(&held.mint).0)
+        }
+    };
+    Ok((Some(token_account), metadata))
+}
+
+/// Associated token account of `owner` for `mint`, under either token program
+fn find_associated_account(program: &AuctionProgram, owner: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+    let rpc = program.rpc();
+    [anchor_spl::token::ID, anchor_spl::token_2022::ID]
+        .iter()
+        .map(|token_program| get_associated_token_address_with_program_id(owner, mint, token_program))
+        .find(|address| rpc.get_account(address).is_ok())
+        .ok_or_else(|| anyhow!("{} has no token account for gate mint {}", owner, mint))
+}
+
+fn allowlist_pda(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"allowlist", auction.as_ref()], &blind_auction::ID).0
+}
@@ -0,0 +1,169 @@
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey, system_program};
+use anyhow::Result;
+use blind_auction::{
+    accounts, instruction,
+    state::{Config, ConfigParams, VerifiedCategory},
+};
+use clap::{Args, Subcommand};
+
+use crate::{build_ix, display, lamports, AuctionProgram};
+
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey = pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Show the protocol config
+    Show,
+    /// Create the protocol config (program upgrade authority only)
+    Init(ConfigArgs),
+    /// Replace the protocol config (admin only)
+    Update(ConfigArgs),
+    /// Propose a new admin, who must then run `config accept-admin`
+    TransferAdmin { new_admin: Pubkey },
+    /// Become admin after a transfer was proposed to you
+    AcceptAdmin,
+    /// Stop new auctions and bids (admin or guardian)
+    Pause,
+    /// Allow new auctions and bids again (admin only)
+    Unpause,
+}
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    /// Account allowed to pause the program besides the admin
+    #[arg(long)]
+    guardian: Option<Pubkey>,
+    /// Protocol fee on seller proceeds in basis points
+    #[arg(long, default_value_t = 0)]
+    fee_bps: u16,
+    /// Account receiving protocol fees (defaults to the signer)
+    #[arg(long)]
+    fee_recipient: Option<Pubkey>,
+    /// Lowest minimum bid an auction may set, in SOL
+    #[arg(long, default_value_t = 0.0)]
+    min_minimum_bid: f64,
+    /// Longest allowed auction in hours (0 = unlimited)
+    #[arg(long, default_value_t = 0.0)]
+    max_duration_hours: f64,
+    /// Hours winners have to confirm after close (0 = no deadline)
+    #[arg(long, default_value_t = 0.0)]
+    claim_period_hours: f64,
+    /// Share of a no-show winner's deposit kept for the seller, in basis points
+    #[arg(long, default_value_t = 0)]
+    no_show_penalty_bps: u16,
+    /// Hours an escrowed winner payment waits for delivery confirmation (0 = escrow unavailable)
+    #[arg(long, default_value_t = 0.0)]
+    delivery_timeout_hours: f64,
+    /// Default arbiter for disputes over escrowed payments
+    #[arg(long)]
+    arbiter: Option<Pubkey>,
+    /// Account allowed to verify sellers besides the admin
+    #[arg(long)]
+    verifier: Option<Pubkey>,
+    /// Account allowed to hide and freeze reported auctions besides the admin
+    #[arg(long)]
+    moderator: Option<Pubkey>,
+    /// Allowed category, may be repeated (none = any category)
+    #[arg(long = "category")]
+    categories: Vec<String>,
+    /// CATEGORY=TIER: only sellers verified at TIER or above may list in CATEGORY, may be repeated
+    #[arg(long = "verified-category", value_parser = parse_verified_category)]
+    verified_categories: Vec<VerifiedCategory>,
+}
+
+fn parse_verified_category(value: &str) -> Result<VerifiedCategory, String> {
+    let (category, tier) = value.split_once('=').ok_or("expected CATEGORY=TIER")?;
+    let min_tier = tier.parse().map_err(|_| format!("invalid tier '{}'", tier))?;
+    Ok(VerifiedCategory { category: category.to_string(), min_tier })
+}
+
+impl ConfigArgs {
+    fn into_params(self, signer: Pubkey) -> ConfigParams {
+        ConfigParams {
+            guardian: self.guardian.unwrap_or_default(),
+            fee_bps: self.fee_bps,
+            fee_recipient: self.fee_recipient.unwrap_or(signer),
+            min_minimum_bid: lamports(self.min_minimum_bid),
+            max_duration: (self.max_duration_hours * 3600.0) as i64,
+            claim_period: (self.claim_period_hours * 3600.0) as i64,
+            no_show_penalty_bps: self.no_show_penalty_bps,
+            delivery_timeout: (self.delivery_timeout_hours * 3600.0) as i64,
+            arbiter: self.arbiter.unwrap_or_default(),
+            verifier: self.verifier.unwrap_or_default(),
+            moderator: self.moderator.unwrap_or_default(),
+            allowed_categories: self.categories,
+            verified_categories: self.verified_categories,
+        }
+    }
+}
+
+pub fn run(program: &AuctionProgram, command: ConfigCommand) -> Result<()> {
+    let signer = program.payer();
+    let config = config_pda();
+    let (ix, action) = match command {
+        ConfigCommand::Show => {
+            let account: Config = program.account(config)?;
+            display::print_config(&config, &account);
+            return Ok(());
+        }
+        ConfigCommand::Init(args) => (
+            build_ix(
+                accounts::InitializeConfig {
+                    admin: signer,
+                    config,
+                    program: blind_auction::ID,
+                    program_data: programdata_address(),
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeConfig { params: args.into_params(signer) },
+                vec![],
+            ),
+            "Config initialized",
+        ),
+        ConfigCommand::Update(args) => (
+            build_ix(
+                accounts::UpdateConfig { admin: signer, config },
+                instruction::UpdateConfig { params: args.into_params(signer) },
+                vec![],
+            ),
+            "Config updated",
+        ),
+        ConfigCommand::TransferAdmin { new_admin } => (
+            build_ix(
+                accounts::TransferAdmin { admin: signer, config },
+                instruction::TransferAdmin { new_admin },
+                vec![],
+            ),
+            "Admin transfer proposed",
+        ),
+        ConfigCommand::AcceptAdmin => (
+            build_ix(
+                accounts::AcceptAdmin { pending_admin: signer, config },
+                instruction::AcceptAdmin {},
+                vec![],
+            ),
+            "Admin transfer accepted",
+        ),
+        ConfigCommand::Pause => (set_paused_ix(signer, true), "Program paused"),
+        ConfigCommand::Unpause => (set_paused_ix(signer, false), "Program resumed"),
+    };
+    let sig = program.request().instruction(ix).send()?;
+    println!("{}: {}", action, sig);
+    Ok(())
+}
+
+fn set_paused_ix(signer: Pubkey, paused: bool) -> Instruction {
+    build_ix(
+        accounts::SetPaused { signer, config: config_pda() },
+        instruction::SetPaused { paused },
+        vec![],
+    )
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &blind_auction::ID).0
+}
+
+fn programdata_address() -> Pubkey {
+    Pubkey::find_program_address(&[blind_auction::ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID).0
+}
@@ -0,0 +1,223 @@
+use anchor_client::solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use blind_auction::state::{
+    Auction, Bid, BidderAccess, Comment, CommentPolicy, Config, Dispute, PricingMode, Review, SellerProfile,
+};
+
+pub fn sol(lamports: u64) -> String {
+    format!("{} SOL", lamports as f64 / LAMPORTS_PER_SOL as f64)
+}
+
+pub fn print_auction(address: &Pubkey, auction: &Auction) {
+    println!("Auction {} ({})", auction.auction_id, address);
+    println!("   Title:         {}", auction.title);
+    println!("   Category:      {}", auction.category);
+    println!("   Authority:     {}", auction.authority);
+    println!("   Minimum bid:   {}", sol(auction.minimum_bid));
+    println!("   End time:      {} (unix timestamp)", auction.end_time);
+    println!("   Bidders:       {}", auction.bidder_count);
+    println!("   Status:        {}", status(auction));
+    println!("   Highest bid:   handle {}", auction.highest_bid_handle);
+    if auction.units > 1 || auction.pricing != PricingMode::PayAsBid {
+        println!("   Units:         {} ({:?})", auction.units, auction.pricing);
+    }
+    if let Some(winning_bid) = auction.winning_bid {
+        println!("   Winning bid:   {}", sol(winning_bid));
+    } else if auction.public_result {
+        println!("   Winning bid:   published after close");
+    }
+    for bid in &auction.winning_bids {
+        println!("   Winning bid account: {}", bid);
+    }
+    if auction.claim_expired {
+        println!("   Claims:        expired, unconfirmed winners refunded minus {} bps", auction.no_show_penalty_bps);
+    } else if auction.claim_deadline > 0 {
+        println!("   Claim by:      {} (unix timestamp)", auction.claim_deadline);
+    }
+    if auction.delivery_timeout > 0 {
+        println!("   Escrow:        {} held, released on delivery or after {} hours", sol(auction.escrowed_proceeds), auction.delivery_timeout as f64 / 3600.0);
+        if auction.arbiter != Pubkey::default() {
+            println!("   Arbiter:       {}", auction.arbiter);
+        }
+    }
+    if auction.flag_count > 0 {
+        println!("   Reports:       {}", auction.flag_count);
+    }
+    match (auction.hidden, auction.frozen) {
+        (false, false) => {}
+        (true, false) => println!("   Moderation:    hidden from listings"),
+        (false, true) => println!("   Moderation:    frozen, bidders may refund their deposits"),
+        (true, true) => println!("   Moderation:    hidden and frozen, bidders may refund their deposits"),
+    }
+    if !auction.bid_histogram.is_empty() {
+        println!("   Histogram:     {} buckets (seller only)", auction.bid_histogram.len());
+    }
+    if let Some(price) = auction.clearing_price {
+        println!("   Unit price:    {}", sol(price));
+    }
+    println!("   Protocol fee:  {} bps", auction.fee_bps);
+    match auction.access {
+        BidderAccess::Open => {}
+        BidderAccess::Allowlist => println!("   Access:        invite-only (allowlist account)"),
+        BidderAccess::MerkleRoot(_) => println!("   Access:        invite-only (Merkle root)"),
+    }
+    if let Some(gate) = &auction.token_gate {
+        println!("   Token gate:    {:?} {} (min {})", gate.kind, gate.key, gate.min_amount);
+    }
+    if auction.proceeds > 0 {
+        let state = if auction.settled { "settled" } else { "awaiting settlement" };
+        println!("   Proceeds:      {} ({})", sol(auction.proceeds), state);
+    }
+    if auction.comment_policy != CommentPolicy::Open || auction.comment_interval > 0 {
+        println!("   Comments:      {:?}, one per {} seconds", auction.comment_policy, auction.comment_interval);
+    }
+    if !auction.tags.is_empty() {
+        println!("   Tags:          {}", auction.tags.join(", "));
+    }
+    if !auction.image_url.is_empty() {
+        println!("   Image:         {}", auction.image_url);
+    }
+    if !auction.description.is_empty() {
+        println!("   Description:   {}", auction.description);
+    }
+}
+
+pub fn print_config(address: &Pubkey, config: &Config) {
+    println!("Config ({})", address);
+    println!("   Admin:         {}", config.admin);
+    if config.pending_admin != Pubkey::default() {
+        println!("   Pending admin: {}", config.pending_admin);
+    }
+    if config.guardian != Pubkey::default() {
+        println!("   Guardian:      {}", config.guardian);
+    }
+    println!("   Status:        {}", if config.paused { "paused" } else { "active" });
+    println!("   Fee:           {} bps", config.fee_bps);
+    println!("   Fee recipient: {}", config.fee_recipient);
+    println!("   Minimum bid:   at least {}", sol(config.min_minimum_bid));
+    if config.max_duration > 0 {
+        println!("   Max duration:  {} hours", config.max_duration as f64 / 3600.0);
+    }
+    if config.claim_period > 0 {
+        println!("   Claim period:  {} hours", config.claim_period as f64 / 3600.0);
+        println!("   No-show fee:   {} bps", config.no_show_penalty_bps);
+    }
+    if config.delivery_timeout > 0 {
+        println!("   Escrow:        released {} hours after payment", config.delivery_timeout as f64 / 3600.0);
+    }
+    if config.arbiter != Pubkey::default() {
+        println!("   Arbiter:       {}", config.arbiter);
+    }
+    if config.moderator != Pubkey::default() {
+        println!("   Moderator:     {}", config.moderator);
+    }
+    if config.allowed_categories.is_empty() {
+        println!("   Categories:    any");
+    } else {
+        println!("   Categories:    {}", config.allowed_categories.join(", "));
+    }
+    if config.verifier != Pubkey::default() {
+        println!("   Verifier:      {}", config.verifier);
+    }
+    for requirement in &config.verified_categories {
+        println!("   Verified only: {} (tier {}+)", requirement.category, requirement.min_tier);
+    }
+}
+
+pub fn print_auction_row(address: &Pubkey, auction: &Auction) {
+    println!(
+        "{:>12}  {:<44}  {:<9}  {:>3} bids  {}",
+        auction.auction_id,
+        address.to_string(),
+        status(auction),
+        auction.bidder_count,
+        auction.title
+    );
+}
+
+pub fn print_bid(address: &Pubkey, bid: &Bid) {
+    println!("Bid {}", address);
+    println!("   Bidder:        {}", bid.bidder);
+    println!("   Deposit:       {}", sol(bid.deposit_amount));
+    println!("   Bid handle:    {}", bid.bid_amount_handle);
+    println!("   Checked:       {}", bid.checked);
+    if bid.checked {
+        println!("   Result handle: {}", bid.is_winner_handle);
+    }
+    println!("   Withdrawn:     {}", bid.withdrawn);
+    if bid.escrow_amount > 0 {
+        let state = if bid.disputed { " (disputed)" } else { "" };
+        println!("   Escrow:        {} until {} (unix timestamp){}", sol(bid.escrow_amount), bid.escrow_release_at, state);
+    }
+    if let Some(refund) = bid.dispute_refund {
+        println!("   Ruling:        {} refunded to the buyer", sol(refund));
+    }
+}
+
+pub fn print_dispute(address: &Pubkey, dispute: &Dispute) {
+    println!("Dispute {}", address);
+    println!("   Bid:           {}", dispute.bid);
+    println!("   Opened by:     {} at {}", dispute.opened_by, dispute.opened_at);
+    println!("   Amount:        {}", sol(dispute.amount));
+    for evidence in &dispute.evidence {
+        println!("   Evidence:      {}", evidence);
+    }
+}
+
+pub fn print_seller_profile(address: &Pubkey, profile: &SellerProfile) {
+    println!("Seller {} ({})", profile.authority, address);
+    if !profile.display_name.is_empty() {
+        println!("   Name:          {}", profile.display_name);
+    }
+    if profile.verification_tier > 0 {
+        println!("   Verified:      tier {} since {}", profile.verification_tier, profile.verified_at);
+    }
+    if !profile.bio.is_empty() {
+        println!("   Bio:           {}", profile.bio);
+    }
+    if !profile.avatar_url.is_empty() {
+        println!("   Avatar:        {}", profile.avatar_url);
+    }
+    println!("   Auctions:      {} created, {} settled, {} cancelled", profile.auctions_created, profile.auctions_settled, profile.auctions_cancelled);
+    if profile.rating_count > 0 {
+        let average = profile.rating_total as f64 / profile.rating_count as f64;
+        println!("   Rating:        {:.1}/5 from {} review(s)", average, profile.rating_count);
+    }
+}
+
+pub fn print_review(review: &Review) {
+    println!("Review by {} at {}: {}/5", review.reviewer, review.timestamp, review.score);
+    if !review.text.is_empty() {
+        println!("   {}", review.text);
+    }
+}
+
+/// Print a comment indented `depth` levels under the comment it replies to
+pub fn print_comment(address: &Pubkey, comment: &Comment, depth: usize, is_answer: bool) {
+    let indent = "   ".repeat(depth);
+    let answer = if is_answer { " ✅ answer" } else { "" };
+    println!("{}Comment #{} ({}) by {} at {}{}", indent, comment.comment_id, address, comment.commenter, comment.timestamp, answer);
+    if comment.edited_at > 0 {
+        println!("{}   (edited at {})", indent, comment.edited_at);
+    }
+    println!("{}   {}", indent, comment.comment);
+    if comment.reply_count > 0 {
+        println!("{}   Replies: {}", indent, comment.reply_count);
+    }
+    if comment.flag_count > 0 {
+        println!("{}   Reports: {}", indent, comment.flag_count);
+    }
+}
+
+fn status(auction: &Auction) -> &'static str {
+    if auction.frozen {
+        "frozen"
+    } else if auction.cancelled {
+        "cancelled"
+    } else if auction.is_closed {
+        "closed"
+    } else if auction.is_open {
+        "open"
+    } else {
+        "ended"
+    }
+}
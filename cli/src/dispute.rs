@@ -0,0 +1,112 @@
+use anchor_client::solana_sdk::{pubkey::Pubkey, system_program};
+use anyhow::Result;
+use blind_auction::{accounts, instruction, state::Dispute};
+use clap::Subcommand;
+
+use crate::{auction_pda, bid_pda, build_ix, display, vault_pda, AuctionProgram};
+
+#[derive(Subcommand)]
+pub enum DisputeCommand {
+    /// Show the dispute over a winner's payment
+    Show {
+        auction_id: u64,
+        /// Winner whose payment is disputed (defaults to the signer)
+        #[arg(long)]
+        bidder: Option<Pubkey>,
+    },
+    /// Freeze a winner's escrowed payment until the arbiter rules (seller or buyer)
+    Open {
+        auction_id: u64,
+        /// Reference to off-chain evidence (URL or content hash)
+        evidence: String,
+        /// Winner whose payment is disputed (defaults to the signer)
+        #[arg(long)]
+        bidder: Option<Pubkey>,
+    },
+    /// Attach another evidence reference to an open dispute (seller or buyer)
+    Evidence {
+        auction_id: u64,
+        evidence: String,
+        /// Winner whose payment is disputed (defaults to the signer)
+        #[arg(long)]
+        bidder: Option<Pubkey>,
+    },
+    /// Split the disputed escrow, refunding BUYER_BPS of it to the buyer (arbiter only)
+    Resolve { auction_id: u64, bidder: Pubkey, buyer_bps: u16 },
+}
+
+pub fn run(program: &AuctionProgram, command: DisputeCommand) -> Result<()> {
+    let signer = program.payer();
+    let (ix, action) = match command {
+        DisputeCommand::Show { auction_id, bidder } => {
+            let address = dispute_pda(&bid_pda(&auction_pda(auction_id), &bidder.unwrap_or(signer)));
+            let dispute: Dispute = program.account(address)?;
+            display::print_dispute(&address, &dispute);
+            return Ok(());
+        }
+        DisputeCommand::Open { auction_id, evidence, bidder } => {
+            let auction = auction_pda(auction_id);
+            let bid = bid_pda(&auction, &bidder.unwrap_or(signer));
+            (
+                build_ix(
+                    accounts::OpenDispute {
+                        party: signer,
+                        auction,
+                        bid,
+                        dispute: dispute_pda(&bid),
+                        system_program: system_program::ID,
+                    },
+                    instruction::OpenDispute { evidence },
+                    vec![],
+                ),
+                "Dispute opened",
+            )
+        }
+        DisputeCommand::Evidence { auction_id, evidence, bidder } => {
+            let auction = auction_pda(auction_id);
+            let bid = bid_pda(&auction, &bidder.unwrap_or(signer));
+            (
+                build_ix(
+                    accounts::AddEvidence {
+                        party: signer,
+                        auction,
+                        bid,
+                        dispute: dispute_pda(&bid),
+                    },
+                    instruction::AddEvidence { evidence },
+                    vec![],
+                ),
+                "Evidence added",
+            )
+        }
+        DisputeCommand::Resolve { auction_id, bidder, buyer_bps } => {
+            let auction = auction_pda(auction_id);
+            let bid = bid_pda(&auction, &bidder);
+            let dispute: Dispute = program.account(dispute_pda(&bid))?;
+            (
+                build_ix(
+                    accounts::ResolveDispute {
+                        arbiter: signer,
+                        auction,
+                        bid,
+                        dispute: dispute_pda(&bid),
+                        buyer: bidder,
+                        opener: dispute.opened_by,
+                        vault: vault_pda(&auction),
+                        system_program: system_program::ID,
+                    },
+                    instruction::ResolveDispute { buyer_bps },
+                    vec![],
+                ),
+                "Dispute resolved",
+            )
+        }
+    };
+    let sig = program.request().instruction(ix).send()?;
+    println!("{}: {}", action, sig);
+    Ok(())
+}
+
+fn dispute_pda(bid: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"dispute", bid.as_ref()], &blind_auction::ID).0
+}
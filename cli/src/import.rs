@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// One entry of a batch file such as `dummy-auctions.json`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionSpec {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub image_url: String,
+    /// Minimum bid in SOL
+    pub minimum_bid: f64,
+    pub duration_hours: f64,
+}
+
+pub fn load(path: &Path) -> Result<Vec<AuctionSpec>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("failed to parse {}", path.display()))
+}
@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use blind_auction::constants::INCO_LIGHTNING_ID;
+use serde::Deserialize;
+
+/// Client for the Inco covalidator, backed by the JS SDK through `scripts/inco-bridge.mjs`.
+pub struct IncoClient {
+    node: String,
+    script: PathBuf,
+}
+
+/// Attested decryption of a single handle.
+pub struct Decryption {
+    pub plaintext: String,
+    pub handle_bytes: Vec<u8>,
+    pub plaintext_bytes: Vec<u8>,
+    /// Ed25519 verification instructions that must precede the instruction consuming the result
+    pub ed25519_instructions: Vec<Instruction>,
+}
+
+#[derive(Deserialize)]
+struct EncryptOutput {
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DecryptOutput {
+    plaintext: String,
+    handle_bytes: String,
+    plaintext_bytes: String,
+    ed25519_instructions: Vec<RawInstruction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInstruction {
+    program_id: String,
+    keys: Vec<RawAccountMeta>,
+    data: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl IncoClient {
+    pub fn new(node: String, script: Option<PathBuf>) -> Self {
+        let script = script.unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("scripts/inco-bridge.mjs")
+        });
+        Self { node, script }
+    }
+
+    /// Encrypt a plaintext amount for `new_euint128`
+    pub fn encrypt(&self, value: u64) -> Result<Vec<u8>> {
+        let out: EncryptOutput = self.run(&["encrypt", &value.to_string()])?;
+        decode_hex(&out.ciphertext)
+    }
+
+    /// Request an attested decryption of `handle`, signed by the keypair at `keypair_path`
+    pub fn decrypt(&self, handle: u128, keypair_path: &Path) -> Result<Decryption> {
+        let keypair_path = keypair_path
+            .to_str()
+            .ok_or_else(|| anyhow!("keypair path is not valid UTF-8"))?;
+        let out: DecryptOutput = self.run(&["decrypt", &handle.to_string(), keypair_path])?;
+
+        let ed25519_instructions = out
+            .ed25519_instructions
+            .into_iter()
+            .map(|ix| -> Result<Instruction> {
+                let accounts = ix
+                    .keys
+                    .into_iter()
+                    .map(|k| -> Result<AccountMeta> {
+                        Ok(AccountMeta {
+                            pubkey: k.pubkey.parse()?,
+                            is_signer: k.is_signer,
+                            is_writable: k.is_writable,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Instruction {
+                    program_id: ix.program_id.parse()?,
+                    accounts,
+                    data: decode_hex(&ix.data)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Decryption {
+            plaintext: out.plaintext,
+            handle_bytes: decode_hex(&out.handle_bytes)?,
+            plaintext_bytes: decode_hex(&out.plaintext_bytes)?,
+            ed25519_instructions,
+        })
+    }
+
+    fn run<T: for<'de> Deserialize<'de>>(&self, args: &[&str]) -> Result<T> {
+        let output = Command::new(&self.node)
+            .arg(&self.script)
+            .args(args)
+            .output()
+            .with_context(|| format!("failed to run {} {}", self.node, self.script.display()))?;
+        if !output.status.success() {
+            bail!(
+                "inco bridge failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        serde_json::from_slice(&output.stdout).context("invalid inco bridge output")
+    }
+}
+
+/// Allowance PDA the Inco program uses to record that `allowed` may decrypt `handle`
+pub fn allowance_pda(handle: u128, allowed: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[&handle.to_le_bytes(), allowed.as_ref()],
+        &INCO_LIGHTNING_ID,
+    )
+    .0
+}
+
+/// Remaining accounts granting `allowed` access to `handle`
+pub fn allowance_accounts(handle: u128, allowed: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(allowance_pda(handle, allowed), false),
+        AccountMeta::new_readonly(*allowed, false),
+    ]
+}
+
+/// Extract the handle printed by the program after `prefix` in the transaction logs
+pub fn handle_from_logs(logs: &[String], prefix: &str) -> Option<u128> {
+    logs.iter()
+        .filter_map(|log| log.split_once(prefix))
+        .find_map(|(_, rest)| rest.trim().parse().ok())
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim_start_matches("0x");
+    if !s.len().is_multiple_of(2) {
+        bail!("odd-length hex string");
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(Into::into))
+        .collect()
+}
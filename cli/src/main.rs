@@ -0,0 +1,1266 @@
+mod access;
+mod config;
+mod dispute;
+mod display;
+mod import;
+mod inco;
+mod profile;
+
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::{
+    anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas},
+    solana_client::rpc_filter::{Memcmp, RpcFilterType},
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::{AccountMeta, Instruction},
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature},
+        system_program, sysvar,
+    },
+    Client, Cluster, Program,
+};
+use anyhow::{anyhow, bail, Result};
+use blind_auction::{
+    accounts,
+    constants::INCO_LIGHTNING_ID,
+    instruction,
+    state::{
+        Auction, Bid, BidderAccess, Comment, CommentPolicy, Config, CreateAuctionParams, PricingMode, ReportReason,
+        Review, SellerProfile, TokenGate, TokenGateKind,
+    },
+    vault::bps_share,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::config::{config_pda, ConfigCommand};
+use crate::dispute::DisputeCommand;
+use crate::inco::IncoClient;
+use crate::profile::{seller_profile_pda, ProfileCommand};
+
+type AuctionProgram = Program<Rc<Keypair>>;
+
+#[derive(Parser)]
+#[command(name = "blind-auction-cli", about = "Operate Inco blind auctions from the command line")]
+struct Cli {
+    /// RPC URL or cluster moniker (devnet, localnet, ...)
+    #[arg(long, short = 'u', env = "BLIND_AUCTION_URL", default_value = "devnet")]
+    url: String,
+
+    /// Keypair used to sign and pay for transactions
+    #[arg(long, short = 'k', env = "BLIND_AUCTION_KEYPAIR", default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Node.js binary used to run the Inco bridge script
+    #[arg(long, env = "BLIND_AUCTION_NODE", default_value = "node")]
+    node: String,
+
+    /// Override the path of scripts/inco-bridge.mjs
+    #[arg(long, env = "BLIND_AUCTION_INCO_BRIDGE")]
+    inco_bridge: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Pricing {
+    /// Each winner pays their own deposit
+    PayAsBid,
+    /// Every winner pays the highest losing bid
+    Uniform,
+}
+
+impl From<Pricing> for PricingMode {
+    fn from(pricing: Pricing) -> Self {
+        match pricing {
+            Pricing::PayAsBid => PricingMode::PayAsBid,
+            Pricing::Uniform => PricingMode::Uniform,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Comments {
+    /// Anyone, at any time
+    Open,
+    /// Bidders and the seller
+    BiddersOnly,
+    /// Nobody
+    Disabled,
+    /// Anyone until the auction closes
+    LockedAfterClose,
+}
+
+impl From<Comments> for CommentPolicy {
+    fn from(comments: Comments) -> Self {
+        match comments {
+            Comments::Open => CommentPolicy::Open,
+            Comments::BiddersOnly => CommentPolicy::BiddersOnly,
+            Comments::Disabled => CommentPolicy::Disabled,
+            Comments::LockedAfterClose => CommentPolicy::LockedAfterClose,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Reason {
+    Spam,
+    Fraud,
+    ProhibitedItem,
+    Offensive,
+    Other,
+}
+
+impl From<Reason> for ReportReason {
+    fn from(reason: Reason) -> Self {
+        match reason {
+            Reason::Spam => ReportReason::Spam,
+            Reason::Fraud => ReportReason::Fraud,
+            Reason::ProhibitedItem => ReportReason::ProhibitedItem,
+            Reason::Offensive => ReportReason::Offensive,
+            Reason::Other => ReportReason::Other,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new auction
+    Create {
+        /// Auction id (defaults to the current unix timestamp)
+        #[arg(long)]
+        auction_id: Option<u64>,
+        /// Minimum bid in SOL
+        #[arg(long)]
+        minimum_bid: f64,
+        /// Hours until bidding ends
+        #[arg(long, default_value_t = 24.0)]
+        duration_hours: f64,
+        #[arg(long)]
+        title: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        #[arg(long)]
+        category: String,
+        #[arg(long, default_value = "")]
+        image_url: String,
+        /// Tag, may be repeated
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only let this wallet bid, may be repeated (stored in an allowlist account)
+        #[arg(long = "allow", conflicts_with = "members_file")]
+        allow: Vec<Pubkey>,
+        /// Only let the wallets in this file bid (one pubkey per line, stored as a Merkle root)
+        #[arg(long)]
+        members_file: Option<PathBuf>,
+        /// Only accept bids from holders of this SPL mint
+        #[arg(long, conflicts_with = "gate_collection")]
+        gate_mint: Option<Pubkey>,
+        /// Only accept bids from holders of an NFT in this verified collection
+        #[arg(long)]
+        gate_collection: Option<Pubkey>,
+        /// Minimum token balance for the gate, in base units
+        #[arg(long, default_value_t = 1)]
+        gate_min_amount: u64,
+        /// Identical units for sale; the top bids win one each
+        #[arg(long, default_value_t = 1)]
+        units: u16,
+        /// What winners pay
+        #[arg(long, value_enum, default_value_t = Pricing::PayAsBid)]
+        pricing: Pricing,
+        /// Keep an encrypted count of bids at 1x, 2x, ... this many times the minimum bid
+        #[arg(long, default_value_t = 0)]
+        histogram_buckets: u8,
+        /// Publish the winning bid on-chain after close (see publish-result)
+        #[arg(long)]
+        public_result: bool,
+        /// Hold winner payments in escrow until the buyer confirms delivery or the config's timeout passes
+        #[arg(long)]
+        delivery_escrow: bool,
+        /// Arbiter for disputes over escrowed payments (defaults to the config's arbiter)
+        #[arg(long, requires = "delivery_escrow")]
+        arbiter: Option<Pubkey>,
+        /// Who may comment
+        #[arg(long, value_enum, default_value_t = Comments::Open)]
+        comments: Comments,
+        /// Seconds each commenter waits between comments (0 = no limit)
+        #[arg(long, default_value_t = 0)]
+        comment_interval: i64,
+    },
+    /// Place an encrypted bid
+    Bid {
+        auction_id: u64,
+        /// Bid amount in SOL (encrypted before it leaves this machine)
+        amount: f64,
+        /// Deposit in SOL (defaults to the bid amount)
+        #[arg(long)]
+        deposit: Option<f64>,
+        /// Members file of a Merkle invite-only auction, used to build your proof
+        #[arg(long)]
+        members_file: Option<PathBuf>,
+        /// Token account to present to a token-gated auction
+        #[arg(long)]
+        gate_token_account: Option<Pubkey>,
+    },
+    /// Edit the listing of an auction nobody has bid on; omitted fields are kept (authority only)
+    Update {
+        auction_id: u64,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        image_url: Option<String>,
+        /// Tag, may be repeated (replaces all tags)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// Cancel an auction nobody has bid on (authority only)
+    Cancel { auction_id: u64 },
+    /// Close an auction after its end time (authority only)
+    Close { auction_id: u64 },
+    /// Decrypt and publish the uniform clearing price so winners can withdraw (authority only)
+    RevealPrice { auction_id: u64 },
+    /// Decrypt the winning bid of a closed auction and list confirmed winners (authority only)
+    Result { auction_id: u64 },
+    /// Decrypt and publish the winning bid of a public-result auction (authority only)
+    PublishResult { auction_id: u64 },
+    /// Decrypt the bid histogram of a closed auction (authority only)
+    Depth { auction_id: u64 },
+    /// Compute and decrypt whether your bid won
+    CheckWin { auction_id: u64 },
+    /// Withdraw your refund, or confirm payment if you won
+    Withdraw { auction_id: u64 },
+    /// End the claim period once the deadline passes, refunding no-show winners minus the penalty (authority only)
+    ExpireClaim { auction_id: u64 },
+    /// Once the claim period has expired, keep the no-show penalty of a winner who never withdrew
+    Forfeit { auction_id: u64, bidder: Pubkey },
+    /// Confirm the item you won arrived, releasing your escrowed payment to the seller
+    ConfirmDelivery { auction_id: u64 },
+    /// Release a winner's escrowed payment once the delivery timeout passes (authority only)
+    ReleaseEscrow { auction_id: u64, bidder: Pubkey },
+    /// Pay winner payments received so far out to the seller, minus the protocol fee (authority only)
+    Settle { auction_id: u64 },
+    /// Rate the seller of an auction you won, once it has settled
+    Review {
+        auction_id: u64,
+        /// Score from 1 to 5
+        score: u8,
+        #[arg(default_value = "")]
+        text: String,
+    },
+    /// Comment on an auction
+    Comment {
+        auction_id: u64,
+        text: String,
+        /// Reply to this comment id
+        #[arg(long)]
+        reply_to: Option<u64>,
+    },
+    /// Mark a reply as the official answer to a question (authority only)
+    MarkAnswer { auction_id: u64, question_id: u64, reply_id: u64 },
+    /// Replace the text of your comment
+    EditComment { auction_id: u64, id: u64, text: String },
+    /// Delete a comment (its author, the auction authority, admin or moderator)
+    DeleteComment { auction_id: u64, id: u64 },
+    /// Report an auction, or one of its comments, to the moderators
+    Report {
+        auction_id: u64,
+        reason: Reason,
+        /// Report this comment instead of the auction
+        #[arg(long)]
+        comment: Option<u64>,
+    },
+    /// Hide an auction from listings and/or freeze it (moderator only, auction must be reported)
+    Moderate {
+        auction_id: u64,
+        #[arg(long)]
+        hide: bool,
+        #[arg(long)]
+        freeze: bool,
+    },
+    /// Reclaim your whole deposit from a frozen auction
+    Refund { auction_id: u64 },
+    /// Show an auction with its bids and comments
+    Show { auction_id: u64 },
+    /// List all auctions
+    List {
+        /// Include auctions hidden by moderators
+        #[arg(long)]
+        all: bool,
+    },
+    /// Create every auction described in a JSON batch file (e.g. dummy-auctions.json)
+    Import {
+        file: PathBuf,
+        /// Id of the first auction; subsequent entries use consecutive ids
+        #[arg(long)]
+        first_id: Option<u64>,
+    },
+    /// Manage the protocol config
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Dispute an escrowed winner payment
+    #[command(subcommand)]
+    Dispute(DisputeCommand),
+    /// Manage seller profiles
+    #[command(subcommand)]
+    Profile(ProfileCommand),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = expand_tilde(&cli.keypair);
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("failed to read keypair {}: {}", keypair_path.display(), e))?;
+    let cluster = Cluster::from_str(&cli.url).map_err(|e| anyhow!("invalid url: {}", e))?;
+    let client = Client::new_with_options(cluster, Rc::new(payer), CommitmentConfig::confirmed());
+    let program = client.program(blind_auction::ID)?;
+    let inco = IncoClient::new(cli.node, cli.inco_bridge);
+
+    match cli.command {
+        Command::Create {
+            auction_id,
+            minimum_bid,
+            duration_hours,
+            title,
+            description,
+            category,
+            image_url,
+            tags,
+            allow,
+            members_file,
+            gate_mint,
+            gate_collection,
+            gate_min_amount,
+            units,
+            pricing,
+            histogram_buckets,
+            public_result,
+            delivery_escrow,
+            arbiter,
+            comments,
+            comment_interval,
+        } => {
+            let auction_id = auction_id.unwrap_or_else(|| now() as u64);
+            let end_time = now() + (duration_hours * 3600.0) as i64;
+            let access = match &members_file {
+                Some(path) => access::merkle_access(&access::read_members(path)?)?,
+                None if !allow.is_empty() => BidderAccess::Allowlist,
+                None => BidderAccess::Open,
+            };
+            let token_gate = match (gate_mint, gate_collection) {
+                (Some(key), _) => Some(TokenGate { kind: TokenGateKind::Mint, key, min_amount: gate_min_amount }),
+                (_, Some(key)) => Some(TokenGate { kind: TokenGateKind::Collection, key, min_amount: gate_min_amount }),
+                (None, None) => None,
+            };
+            let params = CreateAuctionParams {
+                access,
+                token_gate,
+                units,
+                pricing: pricing.into(),
+                histogram_buckets,
+                public_result,
+                delivery_escrow,
+                arbiter,
+                comment_policy: comments.into(),
+                comment_interval,
+            };
+            create(
+                &program,
+                auction_id,
+                lamports(minimum_bid),
+                end_time,
+                title,
+                description,
+                category,
+                image_url,
+                tags,
+                params,
+            )?;
+            if access == BidderAccess::Allowlist {
+                access::create_allowlist(&program, auction_pda(auction_id), &allow)?;
+            }
+            Ok(())
+        }
+        Command::Bid { auction_id, amount, deposit, members_file, gate_token_account } => bid(
+            &program,
+            &inco,
+            auction_id,
+            lamports(amount),
+            lamports(deposit.unwrap_or(amount)),
+            members_file.as_deref(),
+            gate_token_account,
+        ),
+        Command::Update { auction_id, title, description, image_url, tags } => {
+            update(&program, auction_id, title, description, image_url, tags)
+        }
+        Command::Cancel { auction_id } => cancel(&program, auction_id),
+        Command::Close { auction_id } => close(&program, auction_id),
+        Command::RevealPrice { auction_id } => reveal_price(&program, &inco, &keypair_path, auction_id),
+        Command::Result { auction_id } => result(&program, &inco, &keypair_path, auction_id),
+        Command::PublishResult { auction_id } => publish_result(&program, &inco, &keypair_path, auction_id),
+        Command::Depth { auction_id } => depth(&program, &inco, &keypair_path, auction_id),
+        Command::CheckWin { auction_id } => check_win(&program, &inco, &keypair_path, auction_id),
+        Command::Withdraw { auction_id } => withdraw(&program, &inco, &keypair_path, auction_id),
+        Command::ExpireClaim { auction_id } => expire_claim(&program, auction_id),
+        Command::Forfeit { auction_id, bidder } => forfeit(&program, &inco, &keypair_path, auction_id, bidder),
+        Command::ConfirmDelivery { auction_id } => confirm_delivery(&program, auction_id),
+        Command::ReleaseEscrow { auction_id, bidder } => release_escrow(&program, auction_id, bidder),
+        Command::Settle { auction_id } => settle(&program, auction_id),
+        Command::Review { auction_id, score, text } => review(&program, auction_id, score, text),
+        Command::Comment { auction_id, text, reply_to } => comment(&program, auction_id, text, reply_to),
+        Command::MarkAnswer { auction_id, question_id, reply_id } => mark_answer(&program, auction_id, question_id, reply_id),
+        Command::EditComment { auction_id, id, text } => edit_comment(&program, auction_id, id, text),
+        Command::DeleteComment { auction_id, id } => delete_comment(&program, auction_id, id),
+        Command::Report { auction_id, reason, comment } => report(&program, auction_id, reason.into(), comment),
+        Command::Moderate { auction_id, hide, freeze } => moderate(&program, auction_id, hide, freeze),
+        Command::Refund { auction_id } => refund(&program, auction_id),
+        Command::Show { auction_id } => show(&program, auction_id),
+        Command::List { all } => list(&program, all),
+        Command::Import { file, first_id } => {
+            let specs = import::load(&file)?;
+            let first_id = first_id.unwrap_or_else(|| now() as u64);
+            for (i, spec) in specs.into_iter().enumerate() {
+                let end_time = now() + (spec.duration_hours * 3600.0) as i64;
+                create(
+                    &program,
+                    first_id + i as u64,
+                    lamports(spec.minimum_bid),
+                    end_time,
+                    spec.title,
+                    spec.description,
+                    spec.category,
+                    spec.image_url,
+                    spec.tags,
+                    CreateAuctionParams::default(),
+                )?;
+            }
+            Ok(())
+        }
+        Command::Config(command) => config::run(&program, command),
+        Command::Dispute(command) => dispute::run(&program, command),
+        Command::Profile(command) => profile::run(&program, command),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create(
+    program: &AuctionProgram,
+    auction_id: u64,
+    minimum_bid: u64,
+    end_time: i64,
+    title: String,
+    description: String,
+    category: String,
+    image_url: String,
+    tags: Vec<String>,
+    params: CreateAuctionParams,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let seller = program.payer();
+    let ix = build_ix(
+        accounts::CreateAuction {
+            authority: seller,
+            config: config_pda(),
+            seller_profile: seller_profile_pda(&seller),
+            auction,
+            vault: vault_pda(&auction),
+            system_program: system_program::ID,
+        },
+        instruction::CreateAuction {
+            auction_id,
+            minimum_bid,
+            end_time,
+            title: title.clone(),
+            description,
+            category,
+            image_url,
+            tags,
+            params,
+        },
+        vec![],
+    );
+
+    let sig = program.request().instruction(ix).send()?;
+    println!("Auction {} created: {}", auction_id, sig);
+    println!("   Title: {}", title);
+    println!("   Address: {}", auction);
+    Ok(())
+}
+
+fn bid(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    auction_id: u64,
+    amount: u64,
+    deposit: u64,
+    members_file: Option<&std::path::Path>,
+    gate_token_account: Option<Pubkey>,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let bidder = program.payer();
+    let auction_account: Auction = program.account(auction)?;
+    let (allowlist, merkle_proof) = access::bid_credentials(&auction, &auction_account, &bidder, members_file)?;
+    let (gate_token_account, gate_metadata) =
+        access::gate_accounts(program, auction_account.token_gate.as_ref(), &bidder, gate_token_account)?;
+    let encrypted_bid_amount = inco.encrypt(amount)?;
+
+    let bid_ix = |remaining| {
+        build_ix(
+            accounts::PlaceBid {
+                bidder,
+                auction,
+                config: config_pda(),
+                allowlist,
+                gate_token_account,
+                gate_metadata,
+                bid: bid_pda(&auction, &bidder),
+                vault: vault_pda(&auction),
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::PlaceBid {
+                encrypted_bid_amount: encrypted_bid_amount.clone(),
+                deposit_amount: deposit,
+                merkle_proof: merkle_proof.clone(),
+            },
+            remaining,
+        )
+    };
+
+    // The bid handle is only known once the Inco program has run, so simulate first
+    let handle = simulate_for_handle(program, bid_ix(vec![]), "Bid handle:")?;
+    let sig = program
+        .request()
+        .instruction(bid_ix(inco::allowance_accounts(handle, &bidder)))
+        .send()?;
+
+    println!("Bid placed: {}", sig);
+    println!("   Deposit: {}", display::sol(deposit));
+    println!("   Bid handle: {}", handle);
+    Ok(())
+}
+
+fn update(
+    program: &AuctionProgram,
+    auction_id: u64,
+    title: Option<String>,
+    description: Option<String>,
+    image_url: Option<String>,
+    tags: Vec<String>,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let current: Auction = program.account(auction)?;
+    let ix = build_ix(
+        accounts::UpdateAuctionMetadata {
+            authority: program.payer(),
+            auction,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateAuctionMetadata {
+            title: title.unwrap_or(current.title),
+            description: description.unwrap_or(current.description),
+            image_url: image_url.unwrap_or(current.image_url),
+            tags: if tags.is_empty() { current.tags } else { tags },
+        },
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Auction {} updated: {}", auction_id, sig);
+    Ok(())
+}
+
+fn cancel(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let ix = build_ix(
+        accounts::CancelAuction {
+            authority: program.payer(),
+            auction: auction_pda(auction_id),
+            seller_profile: seller_profile_pda(&program.payer()),
+        },
+        instruction::CancelAuction {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Auction {} cancelled: {}", auction_id, sig);
+    Ok(())
+}
+
+fn close(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let auction_account: Auction = program.account(auction)?;
+    // The seller is granted the uniform clearing price and the bid histogram
+    let clearing_price_handle = auction_account.clearing_price_handle();
+    let remaining = auction_account
+        .seller_handles()
+        .into_iter()
+        .flat_map(|handle| inco::allowance_accounts(handle, &program.payer()))
+        .collect();
+    let ix = build_ix(
+        accounts::CloseAuction {
+            authority: program.payer(),
+            auction,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::CloseAuction {},
+        remaining,
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Auction {} closed: {}", auction_id, sig);
+    if clearing_price_handle.is_some() {
+        println!("   Run reveal-price {} so winners can withdraw", auction_id);
+    }
+    Ok(())
+}
+
+fn reveal_price(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let auction_account: Auction = program.account(auction)?;
+    if let Some(price) = auction_account.clearing_price {
+        bail!("clearing price already known: {}", display::sol(price));
+    }
+    let handle = auction_account
+        .clearing_price_handle()
+        .ok_or_else(|| anyhow!("auction {} does not use uniform pricing", auction_id))?;
+
+    let result = inco.decrypt(handle, keypair_path)?;
+    let ix = build_ix(
+        accounts::RevealClearingPrice {
+            authority: program.payer(),
+            auction,
+            instructions: sysvar::instructions::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::RevealClearingPrice {
+            clearing_price_handle: result.handle_bytes,
+            clearing_price_plaintext: result.plaintext_bytes,
+        },
+        vec![],
+    );
+
+    let sig = send_attested(program, result.ed25519_instructions, ix)?;
+    println!("Clearing price revealed: {}", sig);
+    println!("   Price: {} lamports per unit", result.plaintext);
+    Ok(())
+}
+
+fn result(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+) -> Result<()> {
+    let auction: Auction = program.account(auction_pda(auction_id))?;
+    if !auction.is_closed {
+        bail!("the winning bid can be decrypted once the auction is closed");
+    }
+
+    let highest = inco.decrypt(auction.highest_bid_handle, keypair_path)?;
+    println!("Auction {} result", auction_id);
+    println!("   Winning bid:   {} lamports", highest.plaintext);
+    if auction.winning_bids.is_empty() {
+        println!("   Winners:       none confirmed yet");
+    }
+    for bid_address in &auction.winning_bids {
+        let bid: Bid = program.account(*bid_address)?;
+        println!("   Winner:        {} (bid {})", bid.bidder, bid_address);
+    }
+    Ok(())
+}
+
+fn publish_result(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let auction_account: Auction = program.account(auction)?;
+    if !auction_account.public_result {
+        bail!("auction {} was not created with --public-result", auction_id);
+    }
+    if let Some(winning_bid) = auction_account.winning_bid {
+        bail!("result already published: {}", display::sol(winning_bid));
+    }
+
+    let result = inco.decrypt(auction_account.highest_bid_handle, keypair_path)?;
+    let ix = build_ix(
+        accounts::PublishResult {
+            authority: program.payer(),
+            auction,
+            instructions: sysvar::instructions::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::PublishResult {
+            highest_bid_handle: result.handle_bytes,
+            highest_bid_plaintext: result.plaintext_bytes,
+        },
+        vec![],
+    );
+
+    let sig = send_attested(program, result.ed25519_instructions, ix)?;
+    println!("Result published: {}", sig);
+    println!("   Winning bid: {} lamports", result.plaintext);
+    Ok(())
+}
+
+fn depth(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+) -> Result<()> {
+    let auction: Auction = program.account(auction_pda(auction_id))?;
+    if auction.bid_histogram.is_empty() {
+        bail!("auction {} has no bid histogram", auction_id);
+    }
+    if !auction.is_closed {
+        bail!("the histogram can be decrypted once the auction is closed");
+    }
+
+    println!("Bid histogram for auction {} ({} bidders)", auction_id, auction.bidder_count);
+    for (threshold, &handle) in auction.histogram_thresholds().zip(&auction.bid_histogram) {
+        let count = inco.decrypt(handle, keypair_path)?;
+        println!("   >= {:<14} {} bids", display::sol(threshold), count.plaintext);
+    }
+    Ok(())
+}
+
+fn check_win(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let bidder = program.payer();
+    let bid = bid_pda(&auction, &bidder);
+
+    let check_ix = |remaining| {
+        build_ix(
+            accounts::CheckWin {
+                bidder,
+                auction,
+                bid,
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::CheckWin {},
+            remaining,
+        )
+    };
+    let handle = simulate_for_handle(program, check_ix(vec![]), "Result handle:")?;
+    let sig = program
+        .request()
+        .instruction(check_ix(inco::allowance_accounts(handle, &bidder)))
+        .send()?;
+    println!("Win status checked: {}", sig);
+
+    let result = inco.decrypt(handle, keypair_path)?;
+    println!("   Won: {}", if result.plaintext == "1" { "yes" } else { "no" });
+    Ok(())
+}
+
+fn withdraw(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let bidder = program.payer();
+    let bid_address = bid_pda(&auction, &bidder);
+    let bid: Bid = program.account(bid_address)?;
+    if !bid.checked {
+        bail!("bid has not been checked yet, run check-win first");
+    }
+
+    let result = inco.decrypt(bid.is_winner_handle, keypair_path)?;
+    let price = if result.plaintext == "1" {
+        let auction_account: Auction = program.account(auction)?;
+        auction_account
+            .winner_price(bid.deposit_amount)
+            .map_err(|_| anyhow!("clearing price not revealed yet, the seller must run reveal-price first"))?
+    } else {
+        0
+    };
+    let ix = build_ix(
+        accounts::WithdrawBid {
+            bidder,
+            auction,
+            bid: bid_address,
+            vault: vault_pda(&auction),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::WithdrawBid {
+            is_winner_handle: result.handle_bytes,
+            is_winner_plaintext: result.plaintext_bytes,
+        },
+        vec![],
+    );
+
+    let sig = send_attested(program, result.ed25519_instructions, ix)?;
+
+    if result.plaintext == "1" {
+        println!("Winner confirmed payment: {}", sig);
+        println!("   Your payment ({}) remains in vault.", display::sol(price));
+        if price < bid.deposit_amount {
+            println!("   {} returned to {}", display::sol(bid.deposit_amount - price), bidder);
+        }
+        let bid: Bid = program.account(bid_address)?;
+        if bid.escrow_amount > 0 {
+            println!("   Held in escrow until you run confirm-delivery (released at {} otherwise)", bid.escrow_release_at);
+        }
+    } else {
+        println!("Refund withdrawn: {}", sig);
+        println!("   {} returned to {}", display::sol(bid.deposit_amount), bidder);
+    }
+    Ok(())
+}
+
+fn expire_claim(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let ix = build_ix(
+        accounts::ExpireClaim { authority: program.payer(), auction: auction_pda(auction_id) },
+        instruction::ExpireClaim {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Claim period expired for auction {}: {}", auction_id, sig);
+    Ok(())
+}
+
+fn forfeit(
+    program: &AuctionProgram,
+    inco: &IncoClient,
+    keypair_path: &std::path::Path,
+    auction_id: u64,
+    bidder: Pubkey,
+) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let checker = program.payer();
+    let bid_address = bid_pda(&auction, &bidder);
+
+    // Recompute the win check so that this wallet may decrypt it too
+    let determine_ix = |remaining| {
+        build_ix(
+            accounts::DetermineWinner {
+                checker,
+                auction,
+                bid: bid_address,
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::DetermineWinner {},
+            remaining,
+        )
+    };
+    let handle = simulate_for_handle(program, determine_ix(vec![]), "Result handle:")?;
+    let mut remaining = inco::allowance_accounts(handle, &bidder);
+    remaining.extend(inco::allowance_accounts(handle, &checker));
+    program.request().instruction(determine_ix(remaining)).send()?;
+
+    let result = inco.decrypt(handle, keypair_path)?;
+    if result.plaintext != "1" {
+        bail!("bid did not win, its bidder reclaims the whole deposit");
+    }
+    let ix = build_ix(
+        accounts::ForfeitBid {
+            caller: checker,
+            auction,
+            bid: bid_address,
+            bidder,
+            vault: vault_pda(&auction),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::ForfeitBid {
+            is_winner_handle: result.handle_bytes,
+            is_winner_plaintext: result.plaintext_bytes,
+        },
+        vec![],
+    );
+    let sig = send_attested(program, result.ed25519_instructions, ix)?;
+
+    let bid: Bid = program.account(bid_address)?;
+    let auction_account: Auction = program.account(auction)?;
+    let penalty = bps_share(bid.deposit_amount, auction_account.no_show_penalty_bps)?;
+    println!("No-show bid forfeited: {}", sig);
+    println!("   Penalty kept for the seller: {}", display::sol(penalty));
+    println!("   {} returned to {}", display::sol(bid.deposit_amount - penalty), bidder);
+    Ok(())
+}
+
+fn confirm_delivery(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let bidder = program.payer();
+    let ix = build_ix(
+        accounts::ConfirmDelivery { bidder, auction, bid: bid_pda(&auction, &bidder) },
+        instruction::ConfirmDelivery {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Delivery confirmed for auction {}: {}", auction_id, sig);
+    Ok(())
+}
+
+fn release_escrow(program: &AuctionProgram, auction_id: u64, bidder: Pubkey) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let ix = build_ix(
+        accounts::ReleaseEscrow { authority: program.payer(), auction, bid: bid_pda(&auction, &bidder) },
+        instruction::ReleaseEscrow {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Escrow released for {} on auction {}: {}", bidder, auction_id, sig);
+    Ok(())
+}
+
+fn settle(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let config: Config = program.account(config_pda())?;
+    let ix = build_ix(
+        accounts::SettleAuction {
+            authority: program.payer(),
+            auction,
+            config: config_pda(),
+            seller_profile: seller_profile_pda(&program.payer()),
+            vault: vault_pda(&auction),
+            fee_recipient: config.fee_recipient,
+            system_program: system_program::ID,
+        },
+        instruction::SettleAuction {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    let settled: Auction = program.account(auction)?;
+    println!("Auction {} settled: {}", auction_id, sig);
+    println!("   Proceeds: {} ({} bps protocol fee)", display::sol(settled.proceeds), settled.fee_bps);
+    Ok(())
+}
+
+fn review(program: &AuctionProgram, auction_id: u64, score: u8, text: String) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let reviewer = program.payer();
+    let auction_account: Auction = program.account(auction)?;
+    let ix = build_ix(
+        accounts::AddReview {
+            reviewer,
+            auction,
+            bid: bid_pda(&auction, &reviewer),
+            seller_profile: seller_profile_pda(&auction_account.authority),
+            review: review_pda(&auction),
+            system_program: system_program::ID,
+        },
+        instruction::AddReview { score, text },
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Review added to auction {}: {}", auction_id, sig);
+    Ok(())
+}
+
+fn comment(program: &AuctionProgram, auction_id: u64, comment_text: String, reply_to: Option<u64>) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    // The program assigns the next id; a concurrent comment makes this one fail and can be retried
+    let auction_account: Auction = program.account(auction)?;
+    let comment_id = auction_account.comment_count;
+    let commenter = program.payer();
+    // Bidders-only auctions check the commenter's bid; rate-limited ones track their last comment
+    let bid = Some(bid_pda(&auction, &commenter)).filter(|bid| program.rpc().get_account(bid).is_ok());
+    let comment_record = Some(comment_record_pda(&auction, &commenter)).filter(|_| auction_account.comment_interval > 0);
+    let ix = build_ix(
+        accounts::AddComment {
+            commenter,
+            auction,
+            comment: comment_pda(&auction, comment_id),
+            parent: reply_to.map(|id| comment_pda(&auction, id)),
+            bid,
+            comment_record,
+            system_program: system_program::ID,
+        },
+        instruction::AddComment { comment_text },
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Comment {} added: {}", comment_id, sig);
+    Ok(())
+}
+
+fn mark_answer(program: &AuctionProgram, auction_id: u64, question_id: u64, reply_id: u64) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let ix = build_ix(
+        accounts::MarkAnswer {
+            authority: program.payer(),
+            auction,
+            question: comment_pda(&auction, question_id),
+            reply: comment_pda(&auction, reply_id),
+        },
+        instruction::MarkAnswer {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Comment {} marked as the answer to {}: {}", reply_id, question_id, sig);
+    Ok(())
+}
+
+fn edit_comment(program: &AuctionProgram, auction_id: u64, comment_id: u64, comment_text: String) -> Result<()> {
+    let ix = build_ix(
+        accounts::EditComment {
+            commenter: program.payer(),
+            auction: auction_pda(auction_id),
+            comment: comment_pda(&auction_pda(auction_id), comment_id),
+            system_program: system_program::ID,
+        },
+        instruction::EditComment { comment_text },
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Comment {} edited: {}", comment_id, sig);
+    Ok(())
+}
+
+fn delete_comment(program: &AuctionProgram, auction_id: u64, comment_id: u64) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let comment = comment_pda(&auction, comment_id);
+    let comment_account: Comment = program.account(comment)?;
+    let ix = build_ix(
+        accounts::DeleteComment {
+            signer: program.payer(),
+            config: config_pda(),
+            auction,
+            comment,
+            commenter: comment_account.commenter,
+            parent: comment_account.parent,
+        },
+        instruction::DeleteComment {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Comment {} deleted: {}", comment_id, sig);
+    Ok(())
+}
+
+fn report(program: &AuctionProgram, auction_id: u64, reason: ReportReason, comment_id: Option<u64>) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let reporter = program.payer();
+    let ix = match comment_id {
+        Some(comment_id) => {
+            let comment = comment_pda(&auction, comment_id);
+            build_ix(
+                accounts::ReportComment {
+                    reporter,
+                    comment,
+                    report: report_pda(&comment, &reporter),
+                    system_program: system_program::ID,
+                },
+                instruction::ReportComment { reason },
+                vec![],
+            )
+        }
+        None => build_ix(
+            accounts::ReportAuction {
+                reporter,
+                auction,
+                report: report_pda(&auction, &reporter),
+                system_program: system_program::ID,
+            },
+            instruction::ReportAuction { reason },
+            vec![],
+        ),
+    };
+    let sig = program.request().instruction(ix).send()?;
+    println!("Report filed: {}", sig);
+    Ok(())
+}
+
+fn moderate(program: &AuctionProgram, auction_id: u64, hidden: bool, frozen: bool) -> Result<()> {
+    let ix = build_ix(
+        accounts::ModerateAuction {
+            moderator: program.payer(),
+            config: config_pda(),
+            auction: auction_pda(auction_id),
+        },
+        instruction::ModerateAuction { hidden, frozen },
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Auction {} moderated (hidden: {}, frozen: {}): {}", auction_id, hidden, frozen, sig);
+    Ok(())
+}
+
+fn refund(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let auction = auction_pda(auction_id);
+    let ix = build_ix(
+        accounts::RefundBid {
+            bidder: program.payer(),
+            auction,
+            bid: bid_pda(&auction, &program.payer()),
+            vault: vault_pda(&auction),
+            system_program: system_program::ID,
+        },
+        instruction::RefundBid {},
+        vec![],
+    );
+    let sig = program.request().instruction(ix).send()?;
+    println!("Deposit refunded: {}", sig);
+    Ok(())
+}
+
+fn show(program: &AuctionProgram, auction_id: u64) -> Result<()> {
+    let address = auction_pda(auction_id);
+    let auction: Auction = program.account(address)?;
+    display::print_auction(&address, &auction);
+
+    let profile_address = seller_profile_pda(&auction.authority);
+    if let Ok(profile) = program.account::<SellerProfile>(profile_address) {
+        println!();
+        display::print_seller_profile(&profile_address, &profile);
+    }
+    if let Ok(review) = program.account::<Review>(review_pda(&address)) {
+        println!();
+        display::print_review(&review);
+    }
+
+    let bids = program.accounts::<Bid>(vec![by_auction(&address)])?;
+    println!();
+    println!("{} bid(s)", bids.len());
+    for (key, bid) in &bids {
+        display::print_bid(key, bid);
+    }
+
+    let comments = fetch_comments(program, &address, auction.comment_count)?;
+    println!();
+    println!("{} comment(s)", comments.len());
+    // Replies are printed under their parent; replies to deleted comments start a thread
+    let keys: Vec<Pubkey> = comments.iter().map(|(key, _)| *key).collect();
+    for (key, comment) in &comments {
+        if !matches!(comment.parent, Some(parent) if keys.contains(&parent)) {
+            print_thread(&comments, key, comment, 0, false);
+        }
+    }
+    Ok(())
+}
+
+/// Comments 0..comment_count of an auction that have not been deleted, in id order
+fn fetch_comments(program: &AuctionProgram, auction: &Pubkey, comment_count: u64) -> Result<Vec<(Pubkey, Comment)>> {
+    let keys: Vec<Pubkey> = (0..comment_count).map(|id| comment_pda(auction, id)).collect();
+    let mut comments = Vec::new();
+    // getMultipleAccounts takes at most 100 keys
+    for chunk in keys.chunks(100) {
+        let accounts = program.rpc().get_multiple_accounts(chunk)?;
+        for (key, account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account {
+                comments.push((*key, Comment::try_deserialize(&mut account.data.as_slice())?));
+            }
+        }
+    }
+    Ok(comments)
+}
+
+fn print_thread(comments: &[(Pubkey, Comment)], address: &Pubkey, comment: &Comment, depth: usize, is_answer: bool) {
+    display::print_comment(address, comment, depth, is_answer);
+    for (key, reply) in comments.iter().filter(|(_, reply)| reply.parent == Some(*address)) {
+        print_thread(comments, key, reply, depth + 1, comment.answer == Some(*key));
+    }
+}
+
+fn list(program: &AuctionProgram, all: bool) -> Result<()> {
+    let mut auctions = program.accounts::<Auction>(vec![])?;
+    auctions.retain(|(_, a)| all || !a.hidden);
+    auctions.sort_by_key(|(_, a)| a.auction_id);
+    for (key, auction) in &auctions {
+        display::print_auction_row(key, auction);
+    }
+    println!("{} auction(s)", auctions.len());
+    Ok(())
+}
+
+/// Simulate `ix` and pull the handle the program logs after `prefix`.
+/// Without its allowance accounts the simulation fails, but only after the handle is logged.
+fn simulate_for_handle(program: &AuctionProgram, ix: Instruction, prefix: &str) -> Result<u128> {
+    let tx = program.request().instruction(ix).signed_transaction()?;
+    let sim = program.rpc().simulate_transaction(&tx)?;
+    let logs = sim.value.logs.unwrap_or_default();
+    inco::handle_from_logs(&logs, prefix).ok_or_else(|| match sim.value.err {
+        Some(err) => anyhow!("simulation failed: {:?}\n{}", err, logs.join("\n")),
+        None => anyhow!("no '{}' in simulation logs", prefix),
+    })
+}
+
+/// Send `ix` behind the Ed25519 instructions attesting its decryption. They must come
+/// first in the same transaction for the program to find and verify them.
+fn send_attested(program: &AuctionProgram, ed25519_instructions: Vec<Instruction>, ix: Instruction) -> Result<Signature> {
+    let mut request = program.request();
+    for ed25519_ix in ed25519_instructions {
+        request = request.instruction(ed25519_ix);
+    }
+    Ok(request.instruction(ix).send()?)
+}
+
+fn build_ix(accounts: impl ToAccountMetas, args: impl InstructionData, remaining: Vec<AccountMeta>) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    Instruction { program_id: blind_auction::ID, accounts: metas, data: args.data() }
+}
+
+/// Match program accounts whose first field (after the discriminator) is `auction`
+fn by_auction(auction: &Pubkey) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, auction.as_ref()))
+}
+
+fn auction_pda(auction_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"auction", &auction_id.to_le_bytes()], &blind_auction::ID).0
+}
+
+fn vault_pda(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", auction.as_ref()], &blind_auction::ID).0
+}
+
+fn bid_pda(auction: &Pubkey, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bid", auction.as_ref(), bidder.as_ref()], &blind_auction::ID).0
+}
+
+fn review_pda(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"review", auction.as_ref()], &blind_auction::ID).0
+}
+
+fn comment_record_pda(auction: &Pubkey, commenter: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"comment_record", auction.as_ref(), commenter.as_ref()], &blind_auction::ID).0
+}
+
+fn report_pda(target: &Pubkey, reporter: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"report", target.as_ref(), reporter.as_ref()], &blind_auction::ID).0
+}
+
+fn comment_pda(auction: &Pubkey, comment_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"comment", auction.as_ref(), &comment_id.to_le_bytes()], &blind_auction::ID).0
+}
+
+fn lamports(sol: f64) -> u64 {
+    (sol * LAMPORTS_PER_SOL as f64).round() as u64
+}
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or_default()
+}
+
+fn expand_tilde(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program};
+use anyhow::Result;
+use blind_auction::{accounts, instruction, state::SellerProfile};
+
+use crate::config::config_pda;
+use clap::Subcommand;
+
+use crate::{build_ix, display, AuctionProgram};
+
+#[derive(Subcommand)]
+pub enum ProfileCommand {
+    /// Show a seller profile (defaults to your own)
+    Show { seller: Option<Pubkey> },
+    /// Create or replace your seller profile
+    Set {
+        /// Display name (max 50 chars)
+        #[arg(long)]
+        name: String,
+        /// Bio (max 500 chars)
+        #[arg(long, default_value = "")]
+        bio: String,
+        /// Avatar image URL (max 200 chars)
+        #[arg(long, default_value = "")]
+        avatar_url: String,
+    },
+    /// Set a seller's verification tier (admin or verifier)
+    Verify { seller: Pubkey, tier: u8 },
+    /// Remove a seller's verification (admin or verifier)
+    Revoke { seller: Pubkey },
+}
+
+pub fn run(program: &AuctionProgram, command: ProfileCommand) -> Result<()> {
+    let signer = program.payer();
+    match command {
+        ProfileCommand::Show { seller } => {
+            let address = seller_profile_pda(&seller.unwrap_or(signer));
+            let profile: SellerProfile = program.account(address)?;
+            display::print_seller_profile(&address, &profile);
+            Ok(())
+        }
+        ProfileCommand::Set { name, bio, avatar_url } => {
+            let (ix, action) = if has_profile(program, &signer) {
+                let ix = build_ix(
+                    accounts::UpdateSellerProfile { authority: signer, seller_profile: seller_profile_pda(&signer) },
+                    instruction::UpdateSellerProfile { display_name: name, bio, avatar_url },
+                    vec![],
+                );
+                (ix, "Seller profile updated")
+            } else {
+                (create_profile_ix(signer, name, bio, avatar_url), "Seller profile created")
+            };
+            let sig = program.request().instruction(ix).send()?;
+            println!("{}: {}", action, sig);
+            Ok(())
+        }
+        ProfileCommand::Verify { seller, tier } => {
+            let ix = build_ix(
+                accounts::VerifySeller {
+                    verifier: signer,
+                    config: config_pda(),
+                    seller_profile: seller_profile_pda(&seller),
+                },
+                instruction::VerifySeller { tier },
+                vec![],
+            );
+            let sig = program.request().instruction(ix).send()?;
+            println!("Seller {} verified at tier {}: {}", seller, tier, sig);
+            Ok(())
+        }
+        ProfileCommand::Revoke { seller } => {
+            let ix = build_ix(
+                accounts::RevokeSeller {
+                    verifier: signer,
+                    config: config_pda(),
+                    seller_profile: seller_profile_pda(&seller),
+                },
+                instruction::RevokeSeller {},
+                vec![],
+            );
+            let sig = program.request().instruction(ix).send()?;
+            println!("Verification revoked for {}: {}", seller, sig);
+            Ok(())
+        }
+    }
+}
+
+fn has_profile(program: &AuctionProgram, seller: &Pubkey) -> bool {
+    program.rpc().get_account(&seller_profile_pda(seller)).is_ok()
+}
+
+fn create_profile_ix(seller: Pubkey, display_name: String, bio: String, avatar_url: String) -> Instruction {
+    build_ix(
+        accounts::CreateSellerProfile {
+            authority: seller,
+            seller_profile: seller_profile_pda(&seller),
+            system_program: system_program::ID,
+        },
+        instruction::CreateSellerProfile { display_name, bio, avatar_url },
+        vec![],
+    )
+}
+
+pub fn seller_profile_pda(seller: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"seller", seller.as_ref()], &blind_auction::ID).0
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::metadata::{mpl_token_metadata::accounts::Metadata, MetadataAccount};
+use anchor_spl::token_interface::TokenAccount;
+use crate::error::AuctionError;
+use crate::state::{Allowlist, BidderAccess, TokenGate, TokenGateKind};
+
+// Domain separation so an inner node can never be passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Check that `bidder` may bid under `access`
+pub fn check_bidder(
+    access: &BidderAccess,
+    bidder: &Pubkey,
+    allowlist: Option<&Allowlist>,
+    merkle_proof: &[[u8; 32]],
+) -> Result<()> {
+    match access {
+        BidderAccess::Open => Ok(()),
+        BidderAccess::Allowlist => {
+            let allowlist = allowlist.ok_or(AuctionError::MissingAccount)?;
+            require!(allowlist.bidders.contains(bidder), AuctionError::NotAllowlisted);
+            Ok(())
+        }
+        BidderAccess::MerkleRoot(root) => {
+            require!(verify_merkle_proof(merkle_proof, root, bidder), AuctionError::NotAllowlisted);
+            Ok(())
+        }
+    }
+}
+
+/// Check that `bidder` holds what `gate` asks for. Collection gates also need
+/// the metadata account of the NFT held in `token_account`.
+pub fn check_token_gate(
+    gate: Option<&TokenGate>,
+    bidder: &Pubkey,
+    token_account: Option<&TokenAccount>,
+    metadata: Option<(Pubkey, &MetadataAccount)>,
+) -> Result<()> {
+    let Some(gate) = gate else {
+        return Ok(());
+    };
+    let token_account = token_account.ok_or(AuctionError::MissingAccount)?;
+    require!(token_account.owner == *bidder, AuctionError::InvalidGateAccount);
+
+    match gate.kind {
+        TokenGateKind::Mint => {
+            require!(token_account.mint == gate.key, AuctionError::InvalidGateAccount);
+        }
+        TokenGateKind::Collection => {
+            let (metadata_key, metadata) = metadata.ok_or(AuctionError::MissingAccount)?;
+            let (expected, _) = Metadata::find_pda(&token_account.mint);
+            require!(metadata_key == expected, AuctionError::InvalidGateAccount);
+            let in_collection = metadata
+                .collection
+                .as_ref()
+                .is_some_and(|collection| collection.verified && collection.key == gate.key);
+            require!(in_collection, AuctionError::InvalidGateAccount);
+        }
+    }
+    require!(token_account.amount >= gate.min_amount, AuctionError::GateBalanceTooLow);
+    Ok(())
+}
+
+pub fn merkle_leaf(bidder: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, bidder.as_ref()]).to_bytes()
+}
+
+/// Children are hashed in sorted order, so proofs need no left/right flags
+fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], bidder: &Pubkey) -> bool {
+    let computed = proof.iter().fold(merkle_leaf(bidder), |hash, sibling| merkle_node(&hash, sibling));
+    computed == *root
+}
+
+/// Root of the tree over `members`; an unpaired node is carried up unchanged
+#[cfg(not(target_os = "solana"))]
+pub fn merkle_root(members: &[Pubkey]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = members.iter().map(merkle_leaf).collect();
+    if level.is_empty() {
+        return [0; 32];
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| if pair.len() == 2 { merkle_node(&pair[0], &pair[1]) } else { pair[0] })
+            .collect();
+    }
+    level[0]
+}
+
+/// Proof for `members[index]` against [`merkle_root`]
+#[cfg(not(target_os = "solana"))]
+pub fn merkle_proof(members: &[Pubkey], mut index: usize) -> Vec<[u8; 32]> {
+    let mut level: Vec<[u8; 32]> = members.iter().map(merkle_leaf).collect();
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| if pair.len() == 2 { merkle_node(&pair[0], &pair[1]) } else { pair[0] })
+            .collect();
+        index /= 2;
+    }
+    proof
+}
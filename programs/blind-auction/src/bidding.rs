@@ -0,0 +1,112 @@
+//! Core sealed-bid logic, independent of where the encrypted operations run.
+
+use anchor_lang::prelude::*;
+use inco_lightning::types::{Ebool, Euint128};
+use crate::compute::ConfidentialBackend;
+
+/// Fold a new bid into the running encrypted maximum.
+/// A zero `highest` handle means no bid has been recorded yet.
+pub fn update_highest_bid<'info, B: ConfidentialBackend<'info>>(
+    backend: &mut B,
+    highest: u128,
+    bid: Euint128,
+) -> Result<u128> {
+    if highest == 0 {
+        return Ok(bid.0);
+    }
+
+    // Encrypted comparison: new_bid >= current_highest?
+    let is_higher = backend.e_ge(bid, Euint128(highest))?;
+    // If higher, use new bid; else keep current
+    let new_highest = backend.e_select(is_higher, bid, Euint128(highest))?;
+    Ok(new_highest.0)
+}
+
+/// Insert `bid` into `top`, encrypted bids ordered highest first, keeping at
+/// most `capacity` of them. Each slot keeps the larger of itself and the bid
+/// carried down from the slot above; the smaller one moves on to the next slot.
+pub fn insert_top_bid<'info, B: ConfidentialBackend<'info>>(
+    backend: &mut B,
+    top: &mut Vec<u128>,
+    capacity: usize,
+    bid: Euint128,
+) -> Result<()> {
+    let mut carry = bid;
+    for (i, slot) in top.iter_mut().enumerate() {
+        let current = Euint128(*slot);
+        let is_higher = backend.e_ge(carry, current)?;
+        *slot = backend.e_select(is_higher, carry, current)?.0;
+        if i + 1 == capacity {
+            // Whatever would carry on falls off the end
+            return Ok(());
+        }
+        carry = backend.e_select(is_higher, current, carry)?;
+    }
+    if top.len() < capacity {
+        top.push(carry.0);
+    }
+    Ok(())
+}
+
+/// Count `bid` into a cumulative encrypted histogram: bucket `i` counts bids
+/// of at least `(i + 1) * step`. A zero handle means nothing counted yet.
+/// `one` and `zero` hold the encrypted increments, encrypted with the first bid
+/// and reused for every later one.
+pub fn record_in_histogram<'info, B: ConfidentialBackend<'info>>(
+    backend: &mut B,
+    histogram: &mut [u128],
+    step: u64,
+    one: &mut u128,
+    zero: &mut u128,
+    bid: Euint128,
+) -> Result<()> {
+    if histogram.is_empty() {
+        return Ok(());
+    }
+    let one = encrypted_constant(backend, one, 1)?;
+    let zero = encrypted_constant(backend, zero, 0)?;
+    for (i, count) in histogram.iter_mut().enumerate() {
+        let threshold = backend.as_euint128(step as u128 * (i as u128 + 1))?;
+        let reached = backend.e_ge(bid, threshold)?;
+        let increment = backend.e_select(reached, one, zero)?;
+        *count = if *count == 0 {
+            increment.0
+        } else {
+            backend.e_add(Euint128(*count), increment)?.0
+        };
+    }
+    Ok(())
+}
+
+/// Encrypt `value` into `handle` unless an earlier call already did
+fn encrypted_constant<'info, B: ConfidentialBackend<'info>>(
+    backend: &mut B,
+    handle: &mut u128,
+    value: u128,
+) -> Result<Euint128> {
+    if *handle == 0 {
+        *handle = backend.as_euint128(value)?.0;
+    }
+    Ok(Euint128(*handle))
+}
+
+/// Most instructions in the trace of a `place_bid` transaction: the two compute
+/// budget instructions clients send with it, `place_bid` itself, bid account creation,
+/// the deposit transfer, registering the bid and granting it to the bidder (two, as
+/// Inco creates the allowance account), then a comparison and two selects per top
+/// bid slot but the last, and an encrypted threshold, comparison, select and add per
+/// histogram bucket.
+pub fn place_bid_trace_length(top_bid_capacity: usize, histogram_buckets: usize) -> usize {
+    8 + (3 * top_bid_capacity).saturating_sub(1) + 4 * histogram_buckets
+}
+
+/// Encrypted flag: does `bid` reach `threshold`, the lowest winning bid?
+/// For a single unit that is the highest bid. Tied bids all reach it, so
+/// `withdraw_bid` only lets the first `units` of them pay.
+pub fn is_winning_bid<'info, B: ConfidentialBackend<'info>>(
+    backend: &mut B,
+    bid: u128,
+    threshold: u128,
+) -> Result<Ebool> {
+    backend.e_ge(Euint128(bid), Euint128(threshold))
+}
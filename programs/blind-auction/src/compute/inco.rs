@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    cpi::{self, accounts::{Allow, Operation, VerifySignature}},
+    types::{Ebool, Euint128},
+};
+use super::{AllowanceAccounts, ConfidentialBackend};
+use crate::error::AuctionError;
+
+/// Backend that performs every operation through CPI into Inco Lightning
+pub struct IncoBackend<'info> {
+    program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+    system_program: Option<AccountInfo<'info>>,
+    instructions: Option<AccountInfo<'info>>,
+}
+
+impl<'info> IncoBackend<'info> {
+    pub fn new(program: AccountInfo<'info>, signer: AccountInfo<'info>) -> Self {
+        Self { program, signer, system_program: None, instructions: None }
+    }
+
+    /// Required for `allow`, which creates the allowance account
+    pub fn with_system_program(mut self, system_program: AccountInfo<'info>) -> Self {
+        self.system_program = Some(system_program);
+        self
+    }
+
+    /// Required for `verify`, which reads the Ed25519 instructions
+    pub fn with_instructions(mut self, instructions: AccountInfo<'info>) -> Self {
+        self.instructions = Some(instructions);
+        self
+    }
+
+    fn operation(&self) -> CpiContext<'_, '_, '_, 'info, Operation<'info>> {
+        CpiContext::new(self.program.clone(), Operation { signer: self.signer.clone() })
+    }
+}
+
+impl<'info> ConfidentialBackend<'info> for IncoBackend<'info> {
+    fn new_euint128(&mut self, ciphertext: Vec<u8>) -> Result<Euint128> {
+        cpi::new_euint128(self.operation(), ciphertext, 0)
+    }
+
+    fn as_euint128(&mut self, value: u128) -> Result<Euint128> {
+        cpi::as_euint128(self.operation(), value)
+    }
+
+    fn e_add(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128> {
+        cpi::e_add(self.operation(), lhs, rhs, 0)
+    }
+
+    fn e_ge(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Ebool> {
+        cpi::e_ge(self.operation(), lhs, rhs, 0)
+    }
+
+    fn e_select(&mut self, condition: Ebool, if_true: Euint128, if_false: Euint128) -> Result<Euint128> {
+        cpi::e_select(self.operation(), condition, if_true, if_false, 0)
+    }
+
+    fn allow(&mut self, handle: u128, allowed: Pubkey, accounts: AllowanceAccounts<'info>) -> Result<()> {
+        let system_program = self.system_program.clone().ok_or(AuctionError::MissingAccount)?;
+        let cpi_ctx = CpiContext::new(self.program.clone(), Allow {
+            allowance_account: accounts.allowance_account,
+            signer: self.signer.clone(),
+            allowed_address: accounts.allowed_address,
+            system_program,
+        });
+        cpi::allow(cpi_ctx, handle, true, allowed)
+    }
+
+    fn verify(&mut self, handles: Vec<Vec<u8>>, plaintexts: Vec<Vec<u8>>) -> Result<()> {
+        let instructions = self.instructions.clone().ok_or(AuctionError::MissingAccount)?;
+        let cpi_ctx = CpiContext::new(self.program.clone(), VerifySignature {
+            instructions,
+            signer: self.signer.clone(),
+        });
+        cpi::is_validsignature(cpi_ctx, handles.len() as u8, Some(handles), Some(plaintexts))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Encrypted operations used by the auction.
+//!
+//! Instructions talk to a [`ConfidentialBackend`] instead of calling the Inco
+//! CPI directly, so the bidding logic in [`crate::bidding`] can run on-chain
+//! against [`IncoBackend`] and off-chain against [`simulator::PlaintextSimulator`].
+
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    types::{Ebool, Euint128},
+    ID as INCO_LIGHTNING_ID,
+};
+use crate::error::AuctionError;
+
+pub mod inco;
+#[cfg(not(target_os = "solana"))]
+pub mod simulator;
+
+pub use inco::IncoBackend;
+
+/// Accounts the Inco program needs to record a decryption allowance
+pub struct AllowanceAccounts<'info> {
+    pub allowance_account: AccountInfo<'info>,
+    pub allowed_address: AccountInfo<'info>,
+}
+
+impl<'info> AllowanceAccounts<'info> {
+    /// Take the `[allowance_account, allowed_address]` pair starting at `index`
+    /// in `remaining_accounts`, checking it grants `handle` to `allowed`.
+    ///
+    /// Handles are new on every call, so clients can't derive the allowance account
+    /// up front. Instructions log each handle before taking its pair: a simulation
+    /// without the pair fails here, but only after the handle is in the logs.
+    pub fn from_remaining(
+        remaining_accounts: &[AccountInfo<'info>],
+        index: usize,
+        handle: u128,
+        allowed: Pubkey,
+    ) -> Result<Self> {
+        let pair = remaining_accounts
+            .get(index..index + 2)
+            .ok_or(AuctionError::MissingAllowanceAccounts)?;
+        require_keys_eq!(pair[1].key(), allowed, AuctionError::InvalidAllowedAddress);
+        require_keys_eq!(
+            pair[0].key(),
+            allowance_address(handle, &allowed),
+            AuctionError::InvalidAllowanceAccount
+        );
+
+        Ok(Self {
+            allowance_account: pair[0].clone(),
+            allowed_address: pair[1].clone(),
+        })
+    }
+}
+
+/// PDA where Inco records that `allowed` may decrypt `handle`
+pub fn allowance_address(handle: u128, allowed: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[&handle.to_le_bytes(), allowed.as_ref()], &INCO_LIGHTNING_ID).0
+}
+
+/// Decimal plaintext of an attested `euint128` decryption
+pub fn parse_plaintext_u64(plaintext: &[u8]) -> Result<u64> {
+    std::str::from_utf8(plaintext)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| error!(AuctionError::InvalidInput))
+}
+
+pub trait ConfidentialBackend<'info> {
+    /// Register a client-encrypted value
+    fn new_euint128(&mut self, ciphertext: Vec<u8>) -> Result<Euint128>;
+
+    /// Encrypt a public value, e.g. a constant to compare against
+    fn as_euint128(&mut self, value: u128) -> Result<Euint128>;
+
+    /// Encrypted `lhs + rhs`
+    fn e_add(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128>;
+
+    /// Encrypted `lhs >= rhs`
+    fn e_ge(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Ebool>;
+
+    /// Encrypted `condition ? if_true : if_false`
+    fn e_select(&mut self, condition: Ebool, if_true: Euint128, if_false: Euint128) -> Result<Euint128>;
+
+    /// Let `allowed` decrypt `handle`
+    fn allow(&mut self, handle: u128, allowed: Pubkey, accounts: AllowanceAccounts<'info>) -> Result<()>;
+
+    /// Check attested decryptions of `handles`
+    fn verify(&mut self, handles: Vec<Vec<u8>>, plaintexts: Vec<Vec<u8>>) -> Result<()>;
+}
@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet};
+
+use anchor_lang::prelude::*;
+use inco_lightning::types::{Ebool, Euint128};
+use super::{AllowanceAccounts, ConfidentialBackend};
+use crate::error::AuctionError;
+
+/// In-memory backend that keeps plaintexts behind opaque handles.
+///
+/// Ciphertexts passed to `new_euint128` are the plaintext as 16 little-endian
+/// bytes (see [`PlaintextSimulator::encrypt`]). Handles start at 1, so 0 keeps
+/// meaning "no value" as it does on-chain.
+#[derive(Default)]
+pub struct PlaintextSimulator {
+    values: HashMap<u128, u128>,
+    allowances: HashSet<(u128, Pubkey)>,
+    next_handle: u128,
+}
+
+impl PlaintextSimulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn encrypt(value: u128) -> Vec<u8> {
+        value.to_le_bytes().to_vec()
+    }
+
+    /// Plaintext behind `handle`, if it was produced by this simulator
+    pub fn decrypt(&self, handle: u128) -> Option<u128> {
+        self.values.get(&handle).copied()
+    }
+
+    pub fn is_allowed(&self, handle: u128, address: &Pubkey) -> bool {
+        self.allowances.contains(&(handle, *address))
+    }
+
+    fn store(&mut self, value: u128) -> u128 {
+        self.next_handle += 1;
+        self.values.insert(self.next_handle, value);
+        self.next_handle
+    }
+
+    fn value(&self, handle: u128) -> Result<u128> {
+        self.decrypt(handle).ok_or_else(|| error!(AuctionError::InvalidInput))
+    }
+}
+
+impl<'info> ConfidentialBackend<'info> for PlaintextSimulator {
+    fn new_euint128(&mut self, ciphertext: Vec<u8>) -> Result<Euint128> {
+        let bytes: [u8; 16] = ciphertext.try_into().map_err(|_| error!(AuctionError::InvalidInput))?;
+        Ok(Euint128(self.store(u128::from_le_bytes(bytes))))
+    }
+
+    fn as_euint128(&mut self, value: u128) -> Result<Euint128> {
+        Ok(Euint128(self.store(value)))
+    }
+
+    fn e_add(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128> {
+        let sum = self.value(lhs.0)?.wrapping_add(self.value(rhs.0)?);
+        Ok(Euint128(self.store(sum)))
+    }
+
+    fn e_ge(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Ebool> {
+        let result = self.value(lhs.0)? >= self.value(rhs.0)?;
+        Ok(Ebool(self.store(result as u128)))
+    }
+
+    fn e_select(&mut self, condition: Ebool, if_true: Euint128, if_false: Euint128) -> Result<Euint128> {
+        Ok(if self.value(condition.0)? != 0 { if_true } else { if_false })
+    }
+
+    fn allow(&mut self, handle: u128, allowed: Pubkey, _accounts: AllowanceAccounts<'info>) -> Result<()> {
+        self.value(handle)?;
+        self.allowances.insert((handle, allowed));
+        Ok(())
+    }
+
+    fn verify(&mut self, handles: Vec<Vec<u8>>, plaintexts: Vec<Vec<u8>>) -> Result<()> {
+        require!(handles.len() == plaintexts.len(), AuctionError::InvalidInput);
+        for (handle, plaintext) in handles.into_iter().zip(plaintexts) {
+            let handle: [u8; 16] = handle.try_into().map_err(|_| error!(AuctionError::InvalidInput))?;
+            let value = self.value(u128::from_le_bytes(handle))?;
+            require!(plaintext == value.to_string().into_bytes(), AuctionError::InvalidInput);
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,24 @@
 use anchor_lang::prelude::*;
 
 pub const INCO_LIGHTNING_ID: Pubkey = pubkey!("5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj");
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Highest protocol fee the admin can set (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+pub const MAX_CATEGORIES: usize = 20;
+pub const MAX_CATEGORY_LEN: usize = 50;
+pub const MAX_ALLOWLIST_SIZE: usize = 100;
+/// Most identical units a single auction can sell
+pub const MAX_UNITS: u16 = 10;
+/// Most buckets in an auction's encrypted bid histogram
+pub const MAX_HISTOGRAM_BUCKETS: u8 = 8;
+/// Solana's limit on instructions in a transaction's trace, CPIs included
+pub const MAX_INSTRUCTION_TRACE: usize = 64;
+/// Longest claim period the admin can set (30 days)
+pub const MAX_CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60;
+/// Longest delivery timeout the admin can set (90 days)
+pub const MAX_DELIVERY_TIMEOUT: i64 = 90 * 24 * 60 * 60;
+/// Most evidence references a dispute can hold
+pub const MAX_EVIDENCE: usize = 8;
+pub const MAX_EVIDENCE_LEN: usize = 200;
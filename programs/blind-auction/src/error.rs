@@ -38,4 +38,116 @@ pub enum AuctionError {
     NoFunds,
     #[msg("Invalid input: string length exceeds maximum")]
     InvalidInput,
+    #[msg("Required account missing")]
+    MissingAccount,
+    #[msg("Allowance accounts missing from remaining accounts")]
+    MissingAllowanceAccounts,
+    #[msg("Allowance account does not match handle and allowed address")]
+    InvalidAllowanceAccount,
+    #[msg("Allowed address does not match the bidder")]
+    InvalidAllowedAddress,
+    #[msg("Fee exceeds the protocol maximum")]
+    FeeTooHigh,
+    #[msg("Category is not allowed by the protocol config")]
+    CategoryNotAllowed,
+    #[msg("Minimum bid is below the protocol limit")]
+    MinimumBidTooLow,
+    #[msg("Auction duration exceeds the protocol limit")]
+    DurationTooLong,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("No proceeds to settle")]
+    NoProceeds,
+    #[msg("Auction already settled")]
+    AlreadySettled,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Bidder is not on the auction's allowlist")]
+    NotAllowlisted,
+    #[msg("Auction does not use an allowlist account")]
+    AllowlistNotEnabled,
+    #[msg("Allowlist is full")]
+    AllowlistFull,
+    #[msg("Token account does not satisfy the auction's token gate")]
+    InvalidGateAccount,
+    #[msg("Token balance is below the auction's token gate")]
+    GateBalanceTooLow,
+    #[msg("Units must be between 1 and the protocol maximum")]
+    InvalidUnits,
+    #[msg("Auction does not use uniform pricing")]
+    NotUniformPricing,
+    #[msg("Clearing price already revealed")]
+    ClearingPriceAlreadyRevealed,
+    #[msg("Clearing price not revealed yet")]
+    ClearingPriceNotRevealed,
+    #[msg("Handle is not the auction's clearing price")]
+    ClearingPriceMismatch,
+    #[msg("Histogram needs a non-zero minimum bid and at most the maximum number of buckets")]
+    InvalidHistogram,
+    #[msg("Auction result is not public")]
+    ResultNotPublic,
+    #[msg("Auction result already published")]
+    ResultAlreadyPublished,
+    #[msg("Handle is not the auction's highest bid")]
+    ResultHandleMismatch,
+    #[msg("Auction has no claim deadline")]
+    NoClaimDeadline,
+    #[msg("Claim period has not ended yet")]
+    ClaimPeriodNotOver,
+    #[msg("Claim already expired")]
+    ClaimAlreadyExpired,
+    #[msg("Every unit has a confirmed winner")]
+    AllWinnersConfirmed,
+    #[msg("Delivery escrow is not enabled in the protocol config")]
+    DeliveryEscrowUnavailable,
+    #[msg("Bid has no payment in escrow")]
+    NothingInEscrow,
+    #[msg("Delivery timeout has not passed yet")]
+    DeliveryTimeoutNotReached,
+    #[msg("Auction has no arbiter")]
+    NoArbiter,
+    #[msg("Escrowed payment is under dispute")]
+    EscrowDisputed,
+    #[msg("Dispute already resolved")]
+    DisputeResolved,
+    #[msg("Dispute holds the maximum number of evidence references")]
+    TooMuchEvidence,
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+    #[msg("Auction has not been settled")]
+    AuctionNotSettled,
+    #[msg("Score must be between 1 and 5")]
+    InvalidScore,
+    #[msg("Seller verification tier is too low for this category")]
+    SellerNotVerified,
+    #[msg("Auction is frozen by a moderator")]
+    AuctionFrozen,
+    #[msg("Auction is not frozen")]
+    AuctionNotFrozen,
+    #[msg("Auction has not been reported")]
+    NotFlagged,
+    #[msg("Parent comment does not match")]
+    ParentMismatch,
+    #[msg("Comment is not a reply to this question")]
+    NotAReply,
+    #[msg("Comments are disabled on this auction")]
+    CommentsDisabled,
+    #[msg("Auction no longer accepts comments")]
+    CommentsLocked,
+    #[msg("Only bidders and the seller may comment on this auction")]
+    CommenterNotBidder,
+    #[msg("Comment interval has not passed since your last comment")]
+    CommentTooSoon,
+    #[msg("Auction limits comment frequency; pass your comment record")]
+    MissingCommentRecord,
+    #[msg("Handle is not the bid's win check result")]
+    WinnerHandleMismatch,
+    #[msg("Auction has refunded deposits and stays frozen")]
+    AuctionRefunded,
+    #[msg("Units and histogram buckets need more encrypted operations per bid than a transaction allows")]
+    TooManyBidOperations,
+    #[msg("Winner payment was already settled to the seller")]
+    PaymentSettled,
 }
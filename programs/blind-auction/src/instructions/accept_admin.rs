@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = pending_admin @ AuctionError::NotPendingAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Step two of an admin handover: the proposed admin takes over
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(config.pending_admin != Pubkey::default(), AuctionError::NotPendingAdmin);
+
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+
+    msg!("Admin transfer accepted");
+    msg!("   Admin: {}", config.admin);
+    Ok(())
+}
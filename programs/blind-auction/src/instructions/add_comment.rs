@@ -1,46 +1,104 @@
 use anchor_lang::prelude::*;
-use crate::state::{Auction, Comment};
+use crate::state::{Auction, Bid, Comment, CommentPolicy, CommentRecord};
 use crate::error::AuctionError;
 
 #[derive(Accounts)]
-#[instruction(comment_id: u64)]
+#[instruction(comment_text: String)]
 pub struct AddComment<'info> {
     #[account(mut)]
     pub commenter: Signer<'info>,
 
-    /// CHECK: Auction account
+    #[account(mut)]
     pub auction: Account<'info, Auction>,
 
     #[account(
         init,
         payer = commenter,
-        space = Comment::SIZE,
-        seeds = [b"comment", auction.key().as_ref(), comment_id.to_le_bytes().as_ref()],
+        space = Comment::space(&comment_text),
+        seeds = [b"comment", auction.key().as_ref(), auction.comment_count.to_le_bytes().as_ref()],
         bump
     )]
     pub comment: Account<'info, Comment>,
 
+    /// Comment being replied to, if any
+    #[account(
+        mut,
+        constraint = parent.auction == auction.key() @ AuctionError::ParentMismatch
+    )]
+    pub parent: Option<Account<'info, Comment>>,
+
+    /// Commenter's bid, required on `CommentPolicy::BiddersOnly` auctions
+    #[account(seeds = [b"bid", auction.key().as_ref(), commenter.key().as_ref()], bump = bid.bump)]
+    pub bid: Option<Account<'info, Bid>>,
+
+    /// Required when the auction sets a `comment_interval`
+    #[account(
+        init_if_needed,
+        payer = commenter,
+        space = CommentRecord::SIZE,
+        seeds = [b"comment_record", auction.key().as_ref(), commenter.key().as_ref()],
+        bump
+    )]
+    pub comment_record: Option<Account<'info, CommentRecord>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<AddComment>,
-    comment_id: u64,
-    comment_text: String,
-) -> Result<()> {
+/// Post a comment under the next id of the auction's `comment_count`
+pub fn handler(ctx: Context<AddComment>, comment_text: String) -> Result<()> {
     // Validate comment length
     require!(comment_text.len() <= 500, AuctionError::InvalidInput);
-    require!(comment_text.len() > 0, AuctionError::InvalidInput);
+    require!(!comment_text.is_empty(), AuctionError::InvalidInput);
 
     let clock = Clock::get()?;
+    let auction = &mut ctx.accounts.auction;
+    let commenter = ctx.accounts.commenter.key();
+    auction.require_comments_open()?;
+    if auction.comment_policy == CommentPolicy::BiddersOnly {
+        require!(
+            commenter == auction.authority || ctx.accounts.bid.is_some(),
+            AuctionError::CommenterNotBidder
+        );
+    }
+
+    if auction.comment_interval > 0 {
+        let (Some(record), Some(bump)) = (ctx.accounts.comment_record.as_mut(), ctx.bumps.comment_record) else {
+            return err!(AuctionError::MissingCommentRecord);
+        };
+        // A record created by this instruction has no commenter yet
+        if record.commenter != Pubkey::default() {
+            let next_allowed = record
+                .last_comment_at
+                .checked_add(auction.comment_interval)
+                .ok_or(AuctionError::MathOverflow)?;
+            require!(clock.unix_timestamp >= next_allowed, AuctionError::CommentTooSoon);
+        }
+        record.auction = auction.key();
+        record.commenter = commenter;
+        record.last_comment_at = clock.unix_timestamp;
+        record.bump = bump;
+    }
+
     let comment = &mut ctx.accounts.comment;
+    let comment_id = auction.comment_count;
+    auction.comment_count = comment_id.checked_add(1).ok_or(AuctionError::MathOverflow)?;
 
-    comment.auction = ctx.accounts.auction.key();
-    comment.commenter = ctx.accounts.commenter.key();
+    comment.auction = auction.key();
+    comment.commenter = commenter;
+    comment.comment_id = comment_id;
     comment.comment = comment_text;
     comment.timestamp = clock.unix_timestamp;
+    comment.edited_at = 0;
+    comment.flag_count = 0;
+    comment.parent = ctx.accounts.parent.as_ref().map(|parent| parent.key());
+    comment.reply_count = 0;
+    comment.answer = None;
     comment.bump = ctx.bumps.comment;
 
-    msg!("Comment {} added to auction {}", comment_id, ctx.accounts.auction.auction_id);
+    msg!("Comment {} added to auction {}", comment_id, auction.auction_id);
+    if let Some(parent) = ctx.accounts.parent.as_mut() {
+        parent.reply_count = parent.reply_count.checked_add(1).ok_or(AuctionError::MathOverflow)?;
+        msg!("   ↩️ Reply to {}", parent.key());
+    }
     Ok(())
 }
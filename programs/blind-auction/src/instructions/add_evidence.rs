@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Bid, Dispute};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct AddEvidence<'info> {
+    pub party: Signer<'info>,
+
+    pub auction: Account<'info, Auction>,
+
+    #[account(has_one = auction)]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", bid.key().as_ref()],
+        bump = dispute.bump,
+        has_one = bid
+    )]
+    pub dispute: Account<'info, Dispute>,
+}
+
+/// Attach another evidence reference to an open dispute
+pub fn handler(ctx: Context<AddEvidence>, evidence: String) -> Result<()> {
+    let party = ctx.accounts.party.key();
+    require!(
+        ctx.accounts.auction.is_dispute_party(&ctx.accounts.bid, &party),
+        AuctionError::Unauthorized
+    );
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.add_evidence(evidence)?;
+
+    msg!("Evidence added to dispute {}", dispute.key());
+    msg!("   From: {}", party);
+    msg!("   References: {}", dispute.evidence.len());
+    Ok(())
+}
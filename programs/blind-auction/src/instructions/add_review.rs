@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Bid, Review, SellerProfile};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct AddReview<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        has_one = auction,
+        constraint = bid.bidder == reviewer.key() @ AuctionError::NotBidder
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [b"seller", auction.authority.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    #[account(
+        init,
+        payer = reviewer,
+        space = Review::SIZE,
+        seeds = [b"review", auction.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// A confirmed winner rates the seller once their payment has been settled
+pub fn handler(ctx: Context<AddReview>, score: u8, text: String) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let bid = &ctx.accounts.bid;
+    require!(auction.winning_bids.contains(&bid.key()), AuctionError::NotWinner);
+    // This winner's own payment has left escrow and been paid out to the seller
+    require!(auction.is_payment_settled(bid), AuctionError::AuctionNotSettled);
+    require!((1..=5).contains(&score), AuctionError::InvalidScore);
+    require!(text.len() <= 500, AuctionError::InvalidInput);
+
+    let profile = &mut ctx.accounts.seller_profile;
+    profile.rating_count = profile.rating_count.checked_add(1).ok_or(AuctionError::MathOverflow)?;
+    profile.rating_total = profile
+        .rating_total
+        .checked_add(score as u64)
+        .ok_or(AuctionError::MathOverflow)?;
+
+    let review = &mut ctx.accounts.review;
+    review.auction = auction.key();
+    review.seller = auction.authority;
+    review.reviewer = ctx.accounts.reviewer.key();
+    review.score = score;
+    review.text = text;
+    review.timestamp = Clock::get()?.unix_timestamp;
+    review.bump = ctx.bumps.review;
+
+    msg!("Review added to auction {}", auction.auction_id);
+    msg!("   Score: {}/5", score);
+    msg!("   Seller reviews: {}", profile.rating_count);
+    Ok(())
+}
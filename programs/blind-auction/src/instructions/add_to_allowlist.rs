@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ALLOWLIST_SIZE;
+use crate::state::{Allowlist, Auction};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct AddToAllowlist<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ AuctionError::Unauthorized)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"allowlist", auction.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+}
+
+/// Invite more bidders; lists larger than one transaction are built in batches
+pub fn handler(ctx: Context<AddToAllowlist>, bidders: Vec<Pubkey>) -> Result<()> {
+    let allowlist = &mut ctx.accounts.allowlist;
+    for bidder in bidders {
+        if !allowlist.bidders.contains(&bidder) {
+            require!(allowlist.bidders.len() < MAX_ALLOWLIST_SIZE, AuctionError::AllowlistFull);
+            allowlist.bidders.push(bidder);
+        }
+    }
+
+    msg!("Allowlist updated for auction {}", ctx.accounts.auction.auction_id);
+    msg!("   Bidders: {}", allowlist.bidders.len());
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, SellerProfile};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"seller", auction.authority.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+}
+
+/// Withdraw an auction nobody has bid on yet
+pub fn handler(ctx: Context<CancelAuction>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    require!(!auction.is_closed, AuctionError::AuctionClosed);
+    require!(auction.bidder_count == 0, AuctionError::AuctionHasBids);
+
+    auction.is_open = false;
+    auction.is_closed = true;
+    auction.cancelled = true;
+    ctx.accounts.seller_profile.auctions_cancelled += 1;
+
+    msg!("Auction {} cancelled", auction.auction_id);
+    Ok(())
+}
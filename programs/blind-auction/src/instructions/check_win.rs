@@ -1,10 +1,7 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
-    program::IncoLightning,
-    types::{Ebool, Euint128},
-    ID as INCO_LIGHTNING_ID,
-};
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::bidding;
+use crate::compute::{AllowanceAccounts, ConfidentialBackend, IncoBackend};
 use crate::state::{Auction, Bid};
 use crate::error::AuctionError;
 
@@ -15,7 +12,7 @@ pub struct CheckWin<'info> {
 
     pub auction: Account<'info, Auction>,
 
-    #[account(mut)]
+    #[account(mut, has_one = auction)]
     pub bid: Account<'info, Bid>,
 
     pub system_program: Program<'info, System>,
@@ -33,34 +30,24 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CheckWin<'info>>) -> Resul
     require!(auction.is_closed, AuctionError::AuctionNotClosed);
     require!(!bid.checked, AuctionError::AlreadyChecked);
 
-    let inco = ctx.accounts.inco_lightning_program.to_account_info();
-    let cpi_ctx = CpiContext::new(inco.clone(), Operation { 
-        signer: ctx.accounts.bidder.to_account_info() 
-    });
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.bidder.to_account_info(),
+    )
+    .with_system_program(ctx.accounts.system_program.to_account_info());
 
-    // Encrypted comparison: bid_amount >= highest_bid?
-    let is_winner: Ebool = cpi::e_ge(
-        cpi_ctx,
-        Euint128(bid.bid_amount_handle),
-        Euint128(auction.highest_bid_handle),
-        0,
-    )?;
+    // Encrypted comparison: bid_amount >= lowest winning bid?
+    let is_winner = bidding::is_winning_bid(&mut backend, bid.bid_amount_handle, auction.winning_threshold_handle())?;
 
     bid.is_winner_handle = is_winner.0;
     bid.checked = true;
 
-    // Allow bidder to see result
-    if ctx.remaining_accounts.len() >= 2 {
-        let cpi_ctx = CpiContext::new(inco, Allow {
-            allowance_account: ctx.remaining_accounts[0].clone(),
-            signer: ctx.accounts.bidder.to_account_info(),
-            allowed_address: ctx.remaining_accounts[1].clone(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-        });
-        cpi::allow(cpi_ctx, is_winner.0, true, bid.bidder)?;
-    }
-
     msg!("Win status checked!");
     msg!("   Result handle: {}", is_winner.0);
+
+    // Allow bidder to see result
+    let allowance = AllowanceAccounts::from_remaining(ctx.remaining_accounts, 0, is_winner.0, bid.bidder)?;
+    backend.allow(is_winner.0, bid.bidder, allowance)?;
+
     Ok(())
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::Auction;
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::compute::{AllowanceAccounts, ConfidentialBackend, IncoBackend};
+use crate::state::{Auction, PricingMode};
 use crate::error::AuctionError;
 
 #[derive(Accounts)]
@@ -9,21 +11,64 @@ pub struct CloseAuction<'info> {
 
     #[account(mut)]
     pub auction: Account<'info, Auction>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
 }
 
-pub fn handler(ctx: Context<CloseAuction>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseAuction<'info>>) -> Result<()> {
     let auction = &mut ctx.accounts.auction;
     let clock = Clock::get()?;
 
     require!(auction.authority == ctx.accounts.authority.key(), AuctionError::Unauthorized);
     require!(!auction.is_closed, AuctionError::AuctionClosed);
+    require!(!auction.frozen, AuctionError::AuctionFrozen);
     require!(clock.unix_timestamp >= auction.end_time, AuctionError::AuctionNotEnded);
     require!(auction.bidder_count > 0, AuctionError::NoBidders);
 
     auction.is_open = false;
     auction.is_closed = true;
+    if auction.claim_period > 0 {
+        auction.claim_deadline = clock
+            .unix_timestamp
+            .checked_add(auction.claim_period)
+            .ok_or(AuctionError::MathOverflow)?;
+    }
 
     msg!("Auction closed!");
     msg!("   Total bidders: {}", auction.bidder_count);
+    if auction.claim_deadline > 0 {
+        msg!("   Winners must confirm by: {} (unix timestamp)", auction.claim_deadline);
+    }
+
+    if auction.pricing == PricingMode::Uniform {
+        match auction.clearing_price_handle() {
+            Some(handle) => msg!("   Clearing price handle: {}", handle),
+            None => {
+                // Every bid wins a unit, so winners pay the minimum bid
+                auction.clearing_price = Some(auction.minimum_bid);
+                msg!("   Clearing price: {} lamports", auction.minimum_bid);
+            }
+        }
+    }
+
+    msg!("   Highest bid handle: {}", auction.highest_bid_handle);
+
+    // Let the seller decrypt the clearing price (to reveal it with `reveal_clearing_price`),
+    // the highest bid (to know the sale price, and publish it with `publish_result`)
+    // and the bid histogram. Nobody else is ever granted these handles.
+    let seller_handles = auction.seller_handles();
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+    )
+    .with_system_program(ctx.accounts.system_program.to_account_info());
+    let authority = auction.authority;
+    for (i, handle) in seller_handles.into_iter().enumerate() {
+        let allowance = AllowanceAccounts::from_remaining(ctx.remaining_accounts, 2 * i, handle, authority)?;
+        backend.allow(handle, authority, allowance)?;
+    }
     Ok(())
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Bid};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct ConfirmDelivery<'info> {
+    pub bidder: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        has_one = auction,
+        constraint = bid.bidder == bidder.key() @ AuctionError::NotBidder
+    )]
+    pub bid: Account<'info, Bid>,
+}
+
+/// Buyer confirms the item arrived, releasing their escrowed payment to the seller
+pub fn handler(ctx: Context<ConfirmDelivery>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let amount = auction.release_escrow(&mut ctx.accounts.bid)?;
+
+    msg!("Delivery confirmed for auction {}", auction.auction_id);
+    msg!("   📦 {} lamports released to the seller.", amount);
+    Ok(())
+}
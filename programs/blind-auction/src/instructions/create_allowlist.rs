@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ALLOWLIST_SIZE;
+use crate::state::{Allowlist, Auction, BidderAccess};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct CreateAllowlist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ AuctionError::Unauthorized)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Allowlist::SIZE,
+        seeds = [b"allowlist", auction.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the allowlist of an invite-only auction with a first batch of bidders
+pub fn handler(ctx: Context<CreateAllowlist>, bidders: Vec<Pubkey>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    require!(auction.access == BidderAccess::Allowlist, AuctionError::AllowlistNotEnabled);
+    require!(bidders.len() <= MAX_ALLOWLIST_SIZE, AuctionError::AllowlistFull);
+
+    let allowlist = &mut ctx.accounts.allowlist;
+    allowlist.auction = auction.key();
+    allowlist.bidders = Vec::with_capacity(bidders.len());
+    allowlist.bump = ctx.bumps.allowlist;
+    for bidder in bidders {
+        if !allowlist.bidders.contains(&bidder) {
+            allowlist.bidders.push(bidder);
+        }
+    }
+
+    msg!("Allowlist created for auction {}", auction.auction_id);
+    msg!("   Bidders: {}", allowlist.bidders.len());
+    Ok(())
+}
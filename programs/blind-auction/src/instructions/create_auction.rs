@@ -1,17 +1,47 @@
 use anchor_lang::prelude::*;
-use crate::state::Auction;
+use crate::state::{Auction, BidderAccess, CommentPolicy, Config, CreateAuctionParams, PricingMode, SellerProfile};
 use crate::error::AuctionError;
 
 #[derive(Accounts)]
-#[instruction(auction_id: u64)]
+#[instruction(
+    auction_id: u64,
+    minimum_bid: u64,
+    end_time: i64,
+    title: String,
+    description: String,
+    category: String,
+    image_url: String,
+    tags: Vec<String>,
+    params: CreateAuctionParams
+)]
 pub struct CreateAuction<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Created here for sellers who haven't set up a profile yet
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SellerProfile::SIZE,
+        seeds = [b"seller", authority.key().as_ref()],
+        bump
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
     #[account(
         init,
         payer = authority,
-        space = Auction::SIZE,
+        space = Auction::space(
+            Auction::lists_space(params.units, params.pricing, params.histogram_buckets),
+            &title,
+            &description,
+            &category,
+            &image_url,
+            &tags
+        ),
         seeds = [b"auction", auction_id.to_le_bytes().as_ref()],
         bump
     )]
@@ -24,6 +54,7 @@ pub struct CreateAuction<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateAuction>, 
     auction_id: u64, 
@@ -33,20 +64,50 @@ pub fn handler(
     description: String,
     category: String,
     image_url: String,
-    tags: Vec<String>
+    tags: Vec<String>,
+    params: CreateAuctionParams,
 ) -> Result<()> {
+    require!(!ctx.accounts.config.paused, AuctionError::ProgramPaused);
+
     let clock = Clock::get()?;
     require!(end_time > clock.unix_timestamp, AuctionError::AuctionEnded);
     
-    // Validate string lengths
-    require!(title.len() <= 100, AuctionError::InvalidInput);
-    require!(description.len() <= 1000, AuctionError::InvalidInput);
-    require!(category.len() <= 50, AuctionError::InvalidInput);
-    require!(image_url.len() <= 200, AuctionError::InvalidInput);
-    require!(tags.len() <= 10, AuctionError::InvalidInput);
-    for tag in &tags {
-        require!(tag.len() <= 30, AuctionError::InvalidInput);
+    Auction::validate_metadata(&title, &description, &category, &image_url, &tags)?;
+    params.validate(minimum_bid)?;
+    let CreateAuctionParams {
+        access,
+        token_gate,
+        units,
+        pricing,
+        histogram_buckets,
+        public_result,
+        delivery_escrow,
+        arbiter,
+        comment_policy,
+        comment_interval,
+    } = params;
+
+    // Enforce protocol limits
+    let config = &ctx.accounts.config;
+    require!(config.allows_category(&category), AuctionError::CategoryNotAllowed);
+    require!(
+        ctx.accounts.seller_profile.verification_tier >= config.required_tier(&category),
+        AuctionError::SellerNotVerified
+    );
+    require!(minimum_bid >= config.min_minimum_bid, AuctionError::MinimumBidTooLow);
+    if config.max_duration > 0 {
+        require!(end_time - clock.unix_timestamp <= config.max_duration, AuctionError::DurationTooLong);
     }
+    let fee_bps = config.fee_bps;
+    let claim_period = config.claim_period;
+    let no_show_penalty_bps = config.no_show_penalty_bps;
+    let config_arbiter = config.arbiter;
+    let delivery_timeout = if delivery_escrow {
+        require!(config.delivery_timeout > 0, AuctionError::DeliveryEscrowUnavailable);
+        config.delivery_timeout
+    } else {
+        0
+    };
 
     let auction = &mut ctx.accounts.auction;
     auction.authority = ctx.accounts.authority.key();
@@ -59,15 +120,77 @@ pub fn handler(
     auction.highest_bid_handle = 0;
     auction.winner_determined = false;
     auction.bump = ctx.bumps.auction;
+    auction.fee_bps = fee_bps;
+    auction.proceeds = 0;
+    auction.settled = false;
+    auction.settled_proceeds = 0;
+    auction.cancelled = false;
+    auction.access = access;
+    auction.token_gate = token_gate;
+    auction.units = units;
+    auction.pricing = pricing;
+    auction.top_bid_handles = Vec::new();
+    auction.clearing_price = None;
+    auction.bid_histogram = vec![0; histogram_buckets as usize];
+    auction.histogram_one_handle = 0;
+    auction.histogram_zero_handle = 0;
+    auction.public_result = public_result;
+    auction.winning_bid = None;
+    auction.winning_bids = Vec::new();
+    auction.claim_period = claim_period;
+    auction.no_show_penalty_bps = no_show_penalty_bps;
+    auction.claim_deadline = 0;
+    auction.claim_expired = false;
+    auction.delivery_timeout = delivery_timeout;
+    auction.escrowed_proceeds = 0;
+    auction.arbiter = arbiter.unwrap_or(config_arbiter);
+    auction.flag_count = 0;
+    auction.hidden = false;
+    auction.frozen = false;
+    auction.refunded = false;
+    auction.comment_count = 0;
+    auction.comment_policy = comment_policy;
+    auction.comment_interval = comment_interval;
     auction.title = title;
     auction.description = description;
     auction.category = category;
     auction.image_url = image_url;
     auction.tags = tags;
 
+    let profile = &mut ctx.accounts.seller_profile;
+    if profile.authority == Pubkey::default() {
+        profile.authority = ctx.accounts.authority.key();
+        profile.bump = ctx.bumps.seller_profile;
+    }
+    profile.auctions_created += 1;
+
     msg!("Auction {} created", auction_id);
     msg!("   Title: {}", auction.title);
     msg!("   Minimum bid: {} lamports", minimum_bid);
     msg!("   End time: {} (unix timestamp)", end_time);
+    if units > 1 || pricing != PricingMode::PayAsBid {
+        msg!("   Units: {} ({:?})", units, pricing);
+    }
+    if histogram_buckets > 0 {
+        msg!("   Bid histogram: {} buckets", histogram_buckets);
+    }
+    if public_result {
+        msg!("   Public result: winning bid published after close");
+    }
+    if delivery_escrow {
+        msg!("   Delivery escrow: payments released {} seconds after payment unless confirmed sooner", delivery_timeout);
+        if auction.arbiter != Pubkey::default() {
+            msg!("   Arbiter: {}", auction.arbiter);
+        }
+    }
+    if access != BidderAccess::Open {
+        msg!("   Invite-only: {:?}", access);
+    }
+    if let Some(gate) = &auction.token_gate {
+        msg!("   Token gate: {:?} {} (min {})", gate.kind, gate.key, gate.min_amount);
+    }
+    if comment_policy != CommentPolicy::Open || comment_interval > 0 {
+        msg!("   Comments: {:?}, one per {} seconds", comment_policy, comment_interval);
+    }
     Ok(())
 }
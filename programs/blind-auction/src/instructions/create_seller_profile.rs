@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::SellerProfile;
+
+#[derive(Accounts)]
+pub struct CreateSellerProfile<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SellerProfile::SIZE,
+        seeds = [b"seller", authority.key().as_ref()],
+        bump
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the signer's seller profile; counters start at zero. A profile already
+/// made with the seller's first auction only gets its details filled in.
+pub fn handler(
+    ctx: Context<CreateSellerProfile>,
+    display_name: String,
+    bio: String,
+    avatar_url: String,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.seller_profile;
+    if profile.authority == Pubkey::default() {
+        profile.authority = ctx.accounts.authority.key();
+        profile.auctions_created = 0;
+        profile.auctions_settled = 0;
+        profile.auctions_cancelled = 0;
+        profile.rating_count = 0;
+        profile.rating_total = 0;
+        profile.verification_tier = 0;
+        profile.verified_at = 0;
+        profile.bump = ctx.bumps.seller_profile;
+    }
+    profile.set_details(display_name, bio, avatar_url)?;
+
+    msg!("Seller profile created");
+    msg!("   Seller: {}", profile.authority);
+    msg!("   Name: {}", profile.display_name);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Comment, Config};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct DeleteComment<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        has_one = auction,
+        has_one = commenter,
+        close = commenter
+    )]
+    pub comment: Account<'info, Comment>,
+
+    /// Receives the comment's rent
+    #[account(mut)]
+    pub commenter: SystemAccount<'info>,
+
+    /// CHECK: the comment's parent, required for replies; its reply count and
+    /// answer are only updated while it still exists
+    #[account(mut)]
+    pub parent: Option<UncheckedAccount<'info>>,
+}
+
+/// Remove a comment (author, auction authority, admin or moderator).
+/// The account is closed and its rent goes back to the commenter.
+pub fn handler(ctx: Context<DeleteComment>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == ctx.accounts.commenter.key()
+            || signer == ctx.accounts.auction.authority
+            || ctx.accounts.config.can_moderate(&signer),
+        AuctionError::Unauthorized
+    );
+
+    let comment = &ctx.accounts.comment;
+    if let Some(parent_key) = comment.parent {
+        let parent = ctx.accounts.parent.as_ref().ok_or(AuctionError::ParentMismatch)?;
+        require_keys_eq!(parent.key(), parent_key, AuctionError::ParentMismatch);
+        // A deleted parent has been handed back to the system program
+        if parent.owner == ctx.program_id {
+            let mut data = parent.try_borrow_mut_data()?;
+            let mut parent = Comment::try_deserialize(&mut &data[..])?;
+            parent.reply_count = parent.reply_count.saturating_sub(1);
+            if parent.answer == Some(comment.key()) {
+                parent.answer = None;
+            }
+            parent.try_serialize(&mut &mut data[..])?;
+        }
+    }
+
+    msg!("Comment {} deleted", ctx.accounts.comment.key());
+    msg!("   By: {}", signer);
+    Ok(())
+}
@@ -1,10 +1,7 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
-    program::IncoLightning,
-    types::{Ebool, Euint128},
-    ID as INCO_LIGHTNING_ID,
-};
+use inco_lightning::{program::IncoLightning, types::Ebool, ID as INCO_LIGHTNING_ID};
+use crate::bidding;
+use crate::compute::{AllowanceAccounts, ConfidentialBackend, IncoBackend};
 use crate::state::{Auction, Bid};
 use crate::error::AuctionError;
 
@@ -33,36 +30,42 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DetermineWinner<'info>>) -
     require!(!auction.winner_determined, AuctionError::WinnerAlreadyDetermined);
     require!(bid.auction == auction.key(), AuctionError::NotBidder);
 
-    let inco = ctx.accounts.inco_lightning_program.to_account_info();
-    let cpi_ctx = CpiContext::new(inco.clone(), Operation { 
-        signer: ctx.accounts.checker.to_account_info() 
-    });
+    // Once the claim period has expired the checker may see the result too, so a winner
+    // who never withdrew can have their no-show penalty forfeited with `forfeit_bid`
+    let checker = ctx.accounts.checker.key();
+    let forfeitable = auction.claim_expired && !bid.withdrawn && checker != bid.bidder;
+    // A checked result is what the bidder withdraws with, so it is never replaced
+    require!(!bid.checked || forfeitable, AuctionError::AlreadyChecked);
 
-    // Encrypted comparison: bid_amount >= highest_bid?
-    // If true, this bidder is the winner (or tied for highest)
-    let is_winner: Ebool = cpi::e_ge(
-        cpi_ctx,
-        Euint128(bid.bid_amount_handle),
-        Euint128(auction.highest_bid_handle),
-        0,
-    )?;
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.checker.to_account_info(),
+    )
+    .with_system_program(ctx.accounts.system_program.to_account_info());
+
+    // Encrypted comparison: bid_amount >= lowest winning bid?
+    // If true, this bidder is the winner (or tied with the lowest winner)
+    let is_winner = if bid.checked {
+        Ebool(bid.is_winner_handle)
+    } else {
+        bidding::is_winning_bid(&mut backend, bid.bid_amount_handle, auction.winning_threshold_handle())?
+    };
 
     bid.is_winner_handle = is_winner.0;
 
+    msg!("Winner determination checked!");
+    msg!("   Result handle: {}", is_winner.0);
+    msg!("   Bidder: {}", bid.bidder);
+
     // Allow bidder to see result
-    if ctx.remaining_accounts.len() >= 2 {
-        let cpi_ctx = CpiContext::new(inco, Allow {
-            allowance_account: ctx.remaining_accounts[0].clone(),
-            signer: ctx.accounts.checker.to_account_info(),
-            allowed_address: ctx.remaining_accounts[1].clone(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-        });
+    let allowance = AllowanceAccounts::from_remaining(ctx.remaining_accounts, 0, is_winner.0, bid.bidder)?;
+    backend.allow(is_winner.0, bid.bidder, allowance)?;
 
-        cpi::allow(cpi_ctx, is_winner.0, true, bid.bidder)?;
+    if forfeitable {
+        bid.checked = true;
+        let allowance = AllowanceAccounts::from_remaining(ctx.remaining_accounts, 2, is_winner.0, checker)?;
+        backend.allow(is_winner.0, checker, allowance)?;
     }
 
-    msg!("Winner determination checked!");
-    msg!("   Result handle: {}", is_winner.0);
-    msg!("   Bidder: {}", bid.bidder);
     Ok(())
 }
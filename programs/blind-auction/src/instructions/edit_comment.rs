@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Comment};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+#[instruction(comment_text: String)]
+pub struct EditComment<'info> {
+    #[account(mut)]
+    pub commenter: Signer<'info>,
+
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        has_one = auction,
+        has_one = commenter @ AuctionError::Unauthorized,
+        realloc = Comment::space(&comment_text),
+        realloc::payer = commenter,
+        realloc::zero = false
+    )]
+    pub comment: Account<'info, Comment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the text of a comment (author only) while the auction still takes comments.
+/// The account is resized to the new text, with rent paid by or refunded to the commenter.
+pub fn handler(ctx: Context<EditComment>, comment_text: String) -> Result<()> {
+    require!(comment_text.len() <= 500, AuctionError::InvalidInput);
+    require!(!comment_text.is_empty(), AuctionError::InvalidInput);
+    ctx.accounts.auction.require_comments_open()?;
+
+    let comment = &mut ctx.accounts.comment;
+    comment.comment = comment_text;
+    comment.edited_at = Clock::get()?.unix_timestamp;
+
+    msg!("Comment {} edited", comment.key());
+    msg!("   Edited at: {}", comment.edited_at);
+    Ok(())
+}
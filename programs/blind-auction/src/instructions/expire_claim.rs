@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, PricingMode};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct ExpireClaim<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+}
+
+/// End the claim period once the deadline passes without every unit confirmed.
+/// Winners who have not confirmed can then only reclaim their deposit, minus the
+/// no-show penalty.
+pub fn handler(ctx: Context<ExpireClaim>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let clock = Clock::get()?;
+
+    require!(auction.is_closed, AuctionError::AuctionNotClosed);
+    require!(auction.claim_deadline > 0, AuctionError::NoClaimDeadline);
+    require!(!auction.claim_expired, AuctionError::ClaimAlreadyExpired);
+    require!(clock.unix_timestamp >= auction.claim_deadline, AuctionError::ClaimPeriodNotOver);
+    require!(
+        auction.winning_bids.len() < auction.units as usize,
+        AuctionError::AllWinnersConfirmed
+    );
+    // Uniform winners can't confirm until the clearing price is public
+    if auction.pricing == PricingMode::Uniform {
+        require!(auction.clearing_price.is_some(), AuctionError::ClearingPriceNotRevealed);
+    }
+
+    auction.claim_expired = true;
+
+    msg!("Claim period expired for auction {}", auction.auction_id);
+    msg!("   Confirmed winners: {} of {}", auction.winning_bids.len(), auction.units);
+    msg!("   No-show penalty: {} bps", auction.no_show_penalty_bps);
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::compute::{ConfidentialBackend, IncoBackend};
+use crate::state::{Auction, Bid};
+use crate::error::AuctionError;
+use crate::vault::{bps_share, transfer_from_vault};
+use super::withdraw_bid::parse_plaintext_to_bool;
+
+#[derive(Accounts)]
+pub struct ForfeitBid<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut, has_one = auction)]
+    pub bid: Account<'info, Bid>,
+
+    /// CHECK: receives the deposit minus the penalty, must be the bid's owner
+    #[account(mut, address = bid.bidder @ AuctionError::NotBidder)]
+    pub bidder: AccountInfo<'info>,
+
+    /// CHECK: vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+/// Withdraw a winning bid whose bidder never did before the claim period expired:
+/// the no-show penalty goes to the proceeds and the rest of the deposit back to the
+/// bidder. Anyone holding the decrypted win check may call it, which the seller (or
+/// anyone else) gets from `determine_winner` once the claim has expired.
+pub fn handler(
+    ctx: Context<ForfeitBid>,
+    is_winner_handle: Vec<u8>,
+    is_winner_plaintext: Vec<u8>,
+) -> Result<()> {
+    let bid = &mut ctx.accounts.bid;
+    let auction = &mut ctx.accounts.auction;
+
+    require!(auction.claim_expired, AuctionError::ClaimPeriodNotOver);
+    require!(!auction.frozen, AuctionError::AuctionFrozen);
+    require!(bid.checked, AuctionError::NotChecked);
+    require!(!bid.withdrawn, AuctionError::AlreadyWithdrawn);
+    require!(
+        is_winner_handle == bid.is_winner_handle.to_le_bytes(),
+        AuctionError::WinnerHandleMismatch
+    );
+
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.caller.to_account_info(),
+    )
+    .with_instructions(ctx.accounts.instructions.to_account_info());
+    backend.verify(vec![is_winner_handle], vec![is_winner_plaintext.clone()])?;
+    // Losing bids have nothing to forfeit, their bidders reclaim them in full
+    require!(parse_plaintext_to_bool(&is_winner_plaintext)?, AuctionError::NotWinner);
+
+    let penalty = bps_share(bid.deposit_amount, auction.no_show_penalty_bps)?;
+    let refund = bid.deposit_amount - penalty;
+    bid.withdrawn = true;
+    bid.paid = penalty;
+    auction.proceeds = auction
+        .proceeds
+        .checked_add(penalty)
+        .ok_or(AuctionError::MathOverflow)?;
+    auction.settled = false;
+
+    if refund > 0 {
+        transfer_from_vault(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.bidder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &auction.key(),
+            ctx.bumps.vault,
+            refund,
+        )?;
+    }
+
+    msg!("No-show bid forfeited on auction {}", auction.auction_id);
+    msg!("   Penalty kept for the seller: {} lamports", penalty);
+    msg!("   Returned to bidder {}: {} lamports", bid.bidder, refund);
+    Ok(())
+}
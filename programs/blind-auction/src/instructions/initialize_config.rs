@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::program::BlindAuction;
+use crate::state::{Config, ConfigParams};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SIZE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, BlindAuction>,
+
+    /// Only the program's upgrade authority may create the config
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ AuctionError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    params.validate()?;
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.pending_admin = Pubkey::default();
+    config.paused = false;
+    config.bump = ctx.bumps.config;
+    config.apply(params);
+
+    msg!("Config initialized");
+    msg!("   Admin: {}", config.admin);
+    msg!("   Fee: {} bps to {}", config.fee_bps, config.fee_recipient);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Comment};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct MarkAnswer<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ AuctionError::Unauthorized)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut, has_one = auction)]
+    pub question: Account<'info, Comment>,
+
+    #[account(
+        has_one = auction,
+        constraint = reply.parent == Some(question.key()) @ AuctionError::NotAReply
+    )]
+    pub reply: Account<'info, Comment>,
+}
+
+/// Mark a reply as the official answer to a question on the auction (authority only).
+/// Marking another reply replaces the previous answer.
+pub fn handler(ctx: Context<MarkAnswer>) -> Result<()> {
+    let question = &mut ctx.accounts.question;
+    question.answer = Some(ctx.accounts.reply.key());
+
+    msg!("Answer marked on comment {}", question.key());
+    msg!("   ✅ Answer: {}", ctx.accounts.reply.key());
+    Ok(())
+}
@@ -5,6 +5,37 @@ pub mod determine_winner;
 pub mod check_win;
 pub mod withdraw_bid;
 pub mod add_comment;
+pub mod edit_comment;
+pub mod delete_comment;
+pub mod mark_answer;
+pub mod initialize_config;
+pub mod update_config;
+pub mod transfer_admin;
+pub mod accept_admin;
+pub mod settle_auction;
+pub mod set_paused;
+pub mod create_allowlist;
+pub mod add_to_allowlist;
+pub mod reveal_clearing_price;
+pub mod publish_result;
+pub mod expire_claim;
+pub mod forfeit_bid;
+pub mod confirm_delivery;
+pub mod release_escrow;
+pub mod open_dispute;
+pub mod add_evidence;
+pub mod resolve_dispute;
+pub mod create_seller_profile;
+pub mod update_seller_profile;
+pub mod cancel_auction;
+pub mod update_auction_metadata;
+pub mod add_review;
+pub mod verify_seller;
+pub mod revoke_seller;
+pub mod report_auction;
+pub mod report_comment;
+pub mod moderate_auction;
+pub mod refund_bid;
 
 pub use create_auction::*;
 pub use place_bid::*;
@@ -12,4 +43,35 @@ pub use close_auction::*;
 pub use determine_winner::*;
 pub use check_win::*;
 pub use withdraw_bid::*;
-pub use add_comment::*;
\ No newline at end of file
+pub use add_comment::*;
+pub use edit_comment::*;
+pub use delete_comment::*;
+pub use mark_answer::*;
+pub use initialize_config::*;
+pub use update_config::*;
+pub use transfer_admin::*;
+pub use accept_admin::*;
+pub use settle_auction::*;
+pub use set_paused::*;
+pub use create_allowlist::*;
+pub use add_to_allowlist::*;
+pub use reveal_clearing_price::*;
+pub use publish_result::*;
+pub use expire_claim::*;
+pub use forfeit_bid::*;
+pub use confirm_delivery::*;
+pub use release_escrow::*;
+pub use open_dispute::*;
+pub use add_evidence::*;
+pub use resolve_dispute::*;
+pub use create_seller_profile::*;
+pub use update_seller_profile::*;
+pub use cancel_auction::*;
+pub use update_auction_metadata::*;
+pub use add_review::*;
+pub use verify_seller::*;
+pub use revoke_seller::*;
+pub use report_auction::*;
+pub use report_comment::*;
+pub use moderate_auction::*;
+pub use refund_bid::*;
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Config};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct ModerateAuction<'info> {
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.can_moderate(&moderator.key()) @ AuctionError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+}
+
+/// Hide a reported auction from listings and/or freeze it (admin or moderator).
+/// Freezing blocks new bids, closing, winner payments and settlement, and lets
+/// every bidder reclaim their deposit with `refund_bid`. Hiding can be lifted
+/// again at any time, freezing only until the first refund.
+pub fn handler(ctx: Context<ModerateAuction>, hidden: bool, frozen: bool) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    if hidden || frozen {
+        require!(auction.flag_count > 0, AuctionError::NotFlagged);
+    }
+    require!(frozen || !auction.refunded, AuctionError::AuctionRefunded);
+
+    auction.hidden = hidden;
+    auction.frozen = frozen;
+
+    msg!("Auction {} moderated", auction.auction_id);
+    msg!("   Hidden: {}", hidden);
+    msg!("   Frozen: {}", frozen);
+    msg!("   By: {}", ctx.accounts.moderator.key());
+    Ok(())
+}
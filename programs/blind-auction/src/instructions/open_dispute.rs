@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Bid, Dispute};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub party: Signer<'info>,
+
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut, has_one = auction)]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        init,
+        payer = party,
+        space = Dispute::SIZE,
+        seeds = [b"dispute", bid.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Seller or buyer freezes a winner's escrowed payment until the arbiter rules
+pub fn handler(ctx: Context<OpenDispute>, evidence: String) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let bid = &mut ctx.accounts.bid;
+    let party = ctx.accounts.party.key();
+
+    require!(auction.is_dispute_party(bid, &party), AuctionError::Unauthorized);
+    require!(auction.arbiter != Pubkey::default(), AuctionError::NoArbiter);
+    require!(bid.dispute_refund.is_none(), AuctionError::DisputeResolved);
+    require!(bid.escrow_amount > 0, AuctionError::NothingInEscrow);
+
+    bid.disputed = true;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.auction = auction.key();
+    dispute.bid = bid.key();
+    dispute.opened_by = party;
+    dispute.amount = bid.escrow_amount;
+    dispute.evidence = Vec::new();
+    dispute.opened_at = Clock::get()?.unix_timestamp;
+    dispute.bump = ctx.bumps.dispute;
+    dispute.add_evidence(evidence)?;
+
+    msg!("Dispute opened for auction {}", auction.auction_id);
+    msg!("   Opened by: {}", party);
+    msg!("   Escrow frozen: {} lamports", dispute.amount);
+    msg!("   Arbiter: {}", auction.arbiter);
+    Ok(())
+}
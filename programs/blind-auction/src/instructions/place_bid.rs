@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{self, accounts::{Allow, Operation}},
-    program::IncoLightning,
-    types::{Ebool, Euint128},
-    ID as INCO_LIGHTNING_ID,
-};
-use crate::state::{Auction, Bid};
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token_interface::TokenAccount;
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::access;
+use crate::bidding;
+use crate::compute::{AllowanceAccounts, ConfidentialBackend, IncoBackend};
+use crate::state::{Allowlist, Auction, Bid, Config};
 use crate::error::AuctionError;
 
 #[derive(Accounts)]
@@ -16,6 +16,19 @@ pub struct PlaceBid<'info> {
     #[account(mut)]
     pub auction: Account<'info, Auction>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Required when the auction uses `BidderAccess::Allowlist`
+    #[account(seeds = [b"allowlist", auction.key().as_ref()], bump = allowlist.bump)]
+    pub allowlist: Option<Account<'info, Allowlist>>,
+
+    /// Bidder's token account, required when the auction is token-gated
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Metadata of the NFT in `gate_token_account`, required for collection gates
+    pub gate_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
     #[account(
         init,
         payer = bidder,
@@ -39,12 +52,28 @@ pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
     encrypted_bid_amount: Vec<u8>,
     deposit_amount: u64,
+    merkle_proof: Vec<[u8; 32]>,
 ) -> Result<()> {
+    require!(!ctx.accounts.config.paused, AuctionError::ProgramPaused);
+    access::check_bidder(
+        &ctx.accounts.auction.access,
+        &ctx.accounts.bidder.key(),
+        ctx.accounts.allowlist.as_deref(),
+        &merkle_proof,
+    )?;
+    access::check_token_gate(
+        ctx.accounts.auction.token_gate.as_ref(),
+        &ctx.accounts.bidder.key(),
+        ctx.accounts.gate_token_account.as_deref().map(|a| &**a),
+        ctx.accounts.gate_metadata.as_deref().map(|m| (m.key(), &**m)),
+    )?;
+
     let auction = &mut ctx.accounts.auction;
     let clock = Clock::get()?;
     
     require!(auction.is_open, AuctionError::AuctionClosed);
     require!(!auction.is_closed, AuctionError::AuctionClosed);
+    require!(!auction.frozen, AuctionError::AuctionFrozen);
     require!(clock.unix_timestamp < auction.end_time, AuctionError::AuctionEnded);
     require!(deposit_amount >= auction.minimum_bid, AuctionError::BidTooLow);
 
@@ -63,11 +92,12 @@ pub fn handler<'info>(
     )?;
 
     // Create encrypted bid amount handle
-    let inco = ctx.accounts.inco_lightning_program.to_account_info();
-    let cpi_ctx = CpiContext::new(inco.clone(), Operation { 
-        signer: ctx.accounts.bidder.to_account_info() 
-    });
-    let bid_handle: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_bid_amount, 0)?;
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.bidder.to_account_info(),
+    )
+    .with_system_program(ctx.accounts.system_program.to_account_info());
+    let bid_handle = backend.new_euint128(encrypted_bid_amount)?;
 
     // Store bid
     let bid = &mut ctx.accounts.bid;
@@ -79,48 +109,31 @@ pub fn handler<'info>(
 
     auction.bidder_count += 1;
 
-    // Update highest bid: if first bid, set it; otherwise compare encrypted and update if higher
-    if auction.highest_bid_handle == 0 {
-        auction.highest_bid_handle = bid_handle.0;
-    } else {
-        // Encrypted comparison: new_bid >= current_highest?
-        let cpi_ctx_compare = CpiContext::new(inco.clone(), Operation { 
-            signer: ctx.accounts.bidder.to_account_info() 
-        });
-        let is_higher: Ebool = cpi::e_ge(
-            cpi_ctx_compare,
-            bid_handle,
-            Euint128(auction.highest_bid_handle),
-            0,
-        )?;
-
-        // Use e_select to update: if higher, use new bid; else keep current
-        let cpi_ctx_select = CpiContext::new(inco.clone(), Operation { 
-            signer: ctx.accounts.bidder.to_account_info() 
-        });
-        let new_highest: Euint128 = cpi::e_select(
-            cpi_ctx_select,
-            is_higher,
-            bid_handle,
-            Euint128(auction.highest_bid_handle),
-            0,
-        )?;
-        auction.highest_bid_handle = new_highest.0;
-    }
+    // Fold the bid into the encrypted top bids; the first slot is the highest bid
+    let capacity = auction.top_bid_capacity();
+    bidding::insert_top_bid(&mut backend, &mut auction.top_bid_handles, capacity, bid_handle)?;
+    auction.highest_bid_handle = auction.top_bid_handles[0];
 
-    // Allow bidder to decrypt their bid amount
-    if ctx.remaining_accounts.len() >= 2 {
-        let cpi_ctx = CpiContext::new(inco, Allow {
-            allowance_account: ctx.remaining_accounts[0].clone(),
-            signer: ctx.accounts.bidder.to_account_info(),
-            allowed_address: ctx.remaining_accounts[1].clone(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-        });
-        cpi::allow(cpi_ctx, bid_handle.0, true, ctx.accounts.bidder.key())?;
-    }
+    // Count the bid into the seller's encrypted market-depth histogram, if enabled
+    let step = auction.minimum_bid;
+    let Auction { bid_histogram, histogram_one_handle, histogram_zero_handle, .. } = &mut **auction;
+    bidding::record_in_histogram(
+        &mut backend,
+        bid_histogram,
+        step,
+        histogram_one_handle,
+        histogram_zero_handle,
+        bid_handle,
+    )?;
 
     msg!("Bid placed!");
     msg!("   Bid handle: {}", bid_handle.0);
+
+    // Allow bidder to decrypt their bid amount
+    let bidder = ctx.accounts.bidder.key();
+    let allowance = AllowanceAccounts::from_remaining(ctx.remaining_accounts, 0, bid_handle.0, bidder)?;
+    backend.allow(bid_handle.0, bidder, allowance)?;
+
     msg!("   (Your bid is encrypted - nobody can see it!)");
     Ok(())
 }
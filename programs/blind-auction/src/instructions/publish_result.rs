@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::compute::{parse_plaintext_u64, ConfidentialBackend, IncoBackend};
+use crate::state::Auction;
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct PublishResult<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+/// Record the verified winning bid of a public-result auction on-chain
+pub fn handler(
+    ctx: Context<PublishResult>,
+    highest_bid_handle: Vec<u8>,
+    highest_bid_plaintext: Vec<u8>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    require!(auction.is_closed, AuctionError::AuctionNotClosed);
+    require!(auction.public_result, AuctionError::ResultNotPublic);
+    require!(auction.winning_bid.is_none(), AuctionError::ResultAlreadyPublished);
+    require!(
+        highest_bid_handle == auction.highest_bid_handle.to_le_bytes(),
+        AuctionError::ResultHandleMismatch
+    );
+
+    // Verify the decryption signature on-chain for the highest bid
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+    )
+    .with_instructions(ctx.accounts.instructions.to_account_info());
+    backend.verify(vec![highest_bid_handle], vec![highest_bid_plaintext.clone()])?;
+
+    let winning_bid = parse_plaintext_u64(&highest_bid_plaintext)?;
+    auction.winning_bid = Some(winning_bid);
+
+    msg!("Auction {} result published!", auction.auction_id);
+    msg!("   Winning bid: {} lamports", winning_bid);
+    Ok(())
+}
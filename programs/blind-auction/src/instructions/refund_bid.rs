@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Bid};
+use crate::error::AuctionError;
+use crate::vault::transfer_from_vault;
+
+#[derive(Accounts)]
+pub struct RefundBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        has_one = auction,
+        constraint = bid.bidder == bidder.key() @ AuctionError::NotBidder
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// CHECK: vault PDA
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Return the whole deposit of a bid on a frozen auction, win or lose. Winners and
+/// no-shows who already withdrew get back the payment or penalty left in the vault.
+pub fn handler(ctx: Context<RefundBid>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let bid = &mut ctx.accounts.bid;
+
+    require!(auction.frozen, AuctionError::AuctionNotFrozen);
+    let amount = if bid.withdrawn {
+        // The rest of the deposit was returned at withdraw
+        auction.refund_payment(bid)?
+    } else {
+        require!(bid.deposit_amount > 0, AuctionError::NoFunds);
+        bid.withdrawn = true;
+        bid.deposit_amount
+    };
+
+    // Refunded bids still sit among the top bids, so the auction can't resume
+    auction.refunded = true;
+    transfer_from_vault(
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.bidder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &auction.key(),
+        ctx.bumps.vault,
+        amount,
+    )?;
+
+    msg!("Deposit refunded from frozen auction {}", auction.auction_id);
+    msg!("   💰 {} lamports returned.", amount);
+    Ok(())
+}
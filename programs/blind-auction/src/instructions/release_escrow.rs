@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Bid};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut, has_one = auction)]
+    pub bid: Account<'info, Bid>,
+}
+
+/// Release an escrowed payment the buyer never confirmed once the delivery timeout passes
+pub fn handler(ctx: Context<ReleaseEscrow>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= ctx.accounts.bid.escrow_release_at,
+        AuctionError::DeliveryTimeoutNotReached
+    );
+
+    let auction = &mut ctx.accounts.auction;
+    let amount = auction.release_escrow(&mut ctx.accounts.bid)?;
+
+    msg!("Escrow released for auction {}", auction.auction_id);
+    msg!("   Bidder: {}", ctx.accounts.bid.bidder);
+    msg!("   ⌛ Delivery timeout passed, {} lamports released to the seller.", amount);
+    Ok(())
+}
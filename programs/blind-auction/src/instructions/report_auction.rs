@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Report, ReportReason, ReportTarget};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct ReportAuction<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = Report::SIZE,
+        seeds = [b"report", auction.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, Report>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Flag an auction for moderators
+pub fn handler(ctx: Context<ReportAuction>, reason: ReportReason) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    auction.flag_count = auction.flag_count.checked_add(1).ok_or(AuctionError::MathOverflow)?;
+
+    ctx.accounts.report.init(
+        auction.key(),
+        ReportTarget::Auction,
+        ctx.accounts.reporter.key(),
+        reason,
+        ctx.bumps.report,
+    )?;
+
+    msg!("Auction {} reported", auction.auction_id);
+    msg!("   Reason: {:?}", reason);
+    msg!("   Reports: {}", auction.flag_count);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{Comment, Report, ReportReason, ReportTarget};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct ReportComment<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(mut)]
+    pub comment: Account<'info, Comment>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = Report::SIZE,
+        seeds = [b"report", comment.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, Report>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Flag a comment for moderators
+pub fn handler(ctx: Context<ReportComment>, reason: ReportReason) -> Result<()> {
+    let comment = &mut ctx.accounts.comment;
+    comment.flag_count = comment.flag_count.checked_add(1).ok_or(AuctionError::MathOverflow)?;
+
+    ctx.accounts.report.init(
+        comment.key(),
+        ReportTarget::Comment,
+        ctx.accounts.reporter.key(),
+        reason,
+        ctx.bumps.report,
+    )?;
+
+    msg!("Comment {} reported", comment.key());
+    msg!("   Reason: {:?}", reason);
+    msg!("   Reports: {}", comment.flag_count);
+    Ok(())
+}
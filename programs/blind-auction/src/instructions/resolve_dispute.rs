@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+use crate::state::{Auction, Bid, Dispute};
+use crate::error::AuctionError;
+use crate::vault::{bps_share, transfer_from_vault};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = arbiter @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut, has_one = auction)]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", bid.key().as_ref()],
+        bump = dispute.bump,
+        has_one = bid,
+        close = opener
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: the disputing winner, receives their share of the escrow
+    #[account(mut, address = bid.bidder @ AuctionError::NotBidder)]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: the party who opened the dispute, gets its rent back
+    #[account(mut, address = dispute.opened_by @ AuctionError::Unauthorized)]
+    pub opener: AccountInfo<'info>,
+
+    /// CHECK: vault PDA holding the escrowed payment
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Arbiter splits the disputed escrow: `buyer_bps` of it is refunded to the
+/// buyer and the rest is released to the seller. The ruling is recorded on the
+/// bid and the dispute is closed to the party who opened it.
+pub fn handler(ctx: Context<ResolveDispute>, buyer_bps: u16) -> Result<()> {
+    require!(buyer_bps as u64 <= BPS_DENOMINATOR, AuctionError::InvalidInput);
+
+    let bid = &mut ctx.accounts.bid;
+    let auction = &mut ctx.accounts.auction;
+    let amount = bid.escrow_amount;
+    let buyer_amount = bps_share(amount, buyer_bps)?;
+    let seller_amount = amount - buyer_amount;
+
+    // The buyer's share leaves the proceeds; the seller's share becomes settleable
+    bid.disputed = false;
+    bid.dispute_refund = Some(buyer_amount);
+    bid.paid -= buyer_amount;
+    auction.proceeds = auction
+        .proceeds
+        .checked_sub(buyer_amount)
+        .ok_or(AuctionError::MathOverflow)?;
+    auction.release_escrow(bid)?;
+
+    if buyer_amount > 0 {
+        transfer_from_vault(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &auction.key(),
+            ctx.bumps.vault,
+            buyer_amount,
+        )?;
+    }
+
+    msg!("Dispute resolved for auction {}", auction.auction_id);
+    msg!("   Refunded to buyer: {} lamports", buyer_amount);
+    msg!("   Released to seller: {} lamports", seller_amount);
+    Ok(())
+}
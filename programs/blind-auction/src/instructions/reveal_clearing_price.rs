@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::compute::{parse_plaintext_u64, ConfidentialBackend, IncoBackend};
+use crate::state::{Auction, PricingMode};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct RevealClearingPrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+/// Publish the decrypted uniform price so winners can withdraw
+pub fn handler(
+    ctx: Context<RevealClearingPrice>,
+    clearing_price_handle: Vec<u8>,
+    clearing_price_plaintext: Vec<u8>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    require!(auction.is_closed, AuctionError::AuctionNotClosed);
+    require!(auction.pricing == PricingMode::Uniform, AuctionError::NotUniformPricing);
+    require!(auction.clearing_price.is_none(), AuctionError::ClearingPriceAlreadyRevealed);
+
+    let expected = auction.clearing_price_handle().ok_or(AuctionError::ClearingPriceMismatch)?;
+    require!(
+        clearing_price_handle == expected.to_le_bytes(),
+        AuctionError::ClearingPriceMismatch
+    );
+
+    // Verify the decryption signature on-chain for the clearing price
+    let mut backend = IncoBackend::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+    )
+    .with_instructions(ctx.accounts.instructions.to_account_info());
+    backend.verify(vec![clearing_price_handle], vec![clearing_price_plaintext.clone()])?;
+
+    let price = parse_plaintext_u64(&clearing_price_plaintext)?;
+    auction.clearing_price = Some(price);
+
+    msg!("Clearing price revealed!");
+    msg!("   Price: {} lamports per unit", price);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, SellerProfile};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct RevokeSeller<'info> {
+    pub verifier: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.can_verify(&verifier.key()) @ AuctionError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub seller_profile: Account<'info, SellerProfile>,
+}
+
+/// Remove a seller's verification (admin or verifier). Existing auctions are unaffected.
+pub fn handler(ctx: Context<RevokeSeller>) -> Result<()> {
+    let profile = &mut ctx.accounts.seller_profile;
+    profile.verification_tier = 0;
+    profile.verified_at = Clock::get()?.unix_timestamp;
+
+    msg!("Seller verification revoked");
+    msg!("   Seller: {}", profile.authority);
+    msg!("   By: {}", ctx.accounts.verifier.key());
+    Ok(())
+}
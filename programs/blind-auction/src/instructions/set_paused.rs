@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+/// Pause or resume auction creation and bidding. Closing, checking and
+/// withdrawing are never blocked, so deposits can always be recovered.
+/// The guardian can only pause; resuming is left to the admin.
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let signer = ctx.accounts.signer.key();
+    let is_admin = signer == config.admin;
+    let is_guardian = config.guardian != Pubkey::default() && signer == config.guardian;
+    require!(is_admin || (paused && is_guardian), AuctionError::Unauthorized);
+
+    config.paused = paused;
+
+    msg!("Program {}", if paused { "paused" } else { "resumed" });
+    msg!("   By: {}", signer);
+    Ok(())
+}
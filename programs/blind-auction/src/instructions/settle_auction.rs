@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::state::{Auction, Config, SellerProfile};
+use crate::error::AuctionError;
+use crate::vault::{bps_share, transfer_from_vault};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"seller", auction.authority.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    /// CHECK: vault PDA holding the winner's payment
+    #[account(
+        mut,
+        seeds = [b"vault", auction.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: receives the protocol fee, must match the config
+    #[account(mut, address = config.fee_recipient @ AuctionError::Unauthorized)]
+    pub fee_recipient: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay winner payments out to the seller, minus the protocol fee. Payments in
+/// delivery escrow wait until they are confirmed or released.
+pub fn handler(ctx: Context<SettleAuction>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    require!(auction.is_closed, AuctionError::AuctionNotClosed);
+    require!(!auction.settled, AuctionError::AlreadySettled);
+    require!(!auction.frozen, AuctionError::AuctionFrozen);
+
+    // Multi-unit winners withdraw independently, so only pay out what arrived since the last
+    // settlement, leaving payments still awaiting delivery in escrow
+    let proceeds = auction
+        .proceeds
+        .checked_sub(auction.settled_proceeds)
+        .and_then(|p| p.checked_sub(auction.escrowed_proceeds))
+        .ok_or(AuctionError::MathOverflow)?;
+    require!(proceeds > 0, AuctionError::NoProceeds);
+    let fee = bps_share(proceeds, auction.fee_bps)?;
+    let payout = proceeds.checked_sub(fee).ok_or(AuctionError::MathOverflow)?;
+
+    // Later settlements of the same auction are not counted again
+    if auction.settled_proceeds == 0 {
+        ctx.accounts.seller_profile.auctions_settled += 1;
+    }
+    auction.settled = true;
+    auction.settled_proceeds = auction
+        .settled_proceeds
+        .checked_add(proceeds)
+        .ok_or(AuctionError::MathOverflow)?;
+
+    let auction_key = auction.key();
+    let vault = ctx.accounts.vault.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    if fee > 0 {
+        transfer_from_vault(
+            &vault,
+            &ctx.accounts.fee_recipient.to_account_info(),
+            &system_program,
+            &auction_key,
+            ctx.bumps.vault,
+            fee,
+        )?;
+    }
+    transfer_from_vault(
+        &vault,
+        &ctx.accounts.authority.to_account_info(),
+        &system_program,
+        &auction_key,
+        ctx.bumps.vault,
+        payout,
+    )?;
+
+    msg!("Auction {} settled", auction.auction_id);
+    msg!("   Proceeds: {} lamports", proceeds);
+    msg!("   Protocol fee: {} lamports ({} bps)", fee, auction.fee_bps);
+    msg!("   Paid to seller: {} lamports", payout);
+    Ok(())
+}
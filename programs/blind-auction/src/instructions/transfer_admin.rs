@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AuctionError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Step one of an admin handover: propose `new_admin`, who must then call `accept_admin`.
+/// Proposing `Pubkey::default()` cancels a pending transfer.
+pub fn handler(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = new_admin;
+
+    msg!("Admin transfer proposed");
+    msg!("   Pending admin: {}", new_admin);
+    Ok(())
+}
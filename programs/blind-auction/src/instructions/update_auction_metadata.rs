@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::Auction;
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+#[instruction(title: String, description: String, image_url: String, tags: Vec<String>)]
+pub struct UpdateAuctionMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ AuctionError::Unauthorized,
+        realloc = Auction::space(auction.allocated_lists_space(), &title, &description, &auction.category, &image_url, &tags),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub auction: Account<'info, Auction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace an auction's listing before anyone bids (authority only). The category
+/// stays fixed since it decides who may list. The account is resized to the new
+/// metadata, with rent paid by or refunded to the authority.
+pub fn handler(
+    ctx: Context<UpdateAuctionMetadata>,
+    title: String,
+    description: String,
+    image_url: String,
+    tags: Vec<String>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    require!(!auction.is_closed, AuctionError::AuctionClosed);
+    require!(auction.bidder_count == 0, AuctionError::AuctionHasBids);
+    Auction::validate_metadata(&title, &description, &auction.category, &image_url, &tags)?;
+
+    auction.title = title;
+    auction.description = description;
+    auction.image_url = image_url;
+    auction.tags = tags;
+
+    msg!("Auction {} metadata updated", auction.auction_id);
+    msg!("   Title: {}", auction.title);
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, ConfigParams};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AuctionError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Replace every admin-settable field. Existing auctions keep the fee they were created with.
+pub fn handler(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    params.validate()?;
+
+    let config = &mut ctx.accounts.config;
+    config.apply(params);
+
+    msg!("Config updated");
+    msg!("   Fee: {} bps to {}", config.fee_bps, config.fee_recipient);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::SellerProfile;
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct UpdateSellerProfile<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"seller", authority.key().as_ref()],
+        bump = seller_profile.bump,
+        has_one = authority @ AuctionError::Unauthorized
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+}
+
+/// Replace the display name, bio and avatar; counters are only changed by the program
+pub fn handler(
+    ctx: Context<UpdateSellerProfile>,
+    display_name: String,
+    bio: String,
+    avatar_url: String,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.seller_profile;
+    profile.set_details(display_name, bio, avatar_url)?;
+
+    msg!("Seller profile updated");
+    msg!("   Name: {}", profile.display_name);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, SellerProfile};
+use crate::error::AuctionError;
+
+#[derive(Accounts)]
+pub struct VerifySeller<'info> {
+    pub verifier: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.can_verify(&verifier.key()) @ AuctionError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub seller_profile: Account<'info, SellerProfile>,
+}
+
+/// Set a seller's verification tier (admin or verifier). Higher tiers unlock
+/// the categories in `Config::verified_categories`.
+pub fn handler(ctx: Context<VerifySeller>, tier: u8) -> Result<()> {
+    require!(tier > 0, AuctionError::InvalidInput);
+
+    let profile = &mut ctx.accounts.seller_profile;
+    profile.verification_tier = tier;
+    profile.verified_at = Clock::get()?.unix_timestamp;
+
+    msg!("Seller verified");
+    msg!("   Seller: {}", profile.authority);
+    msg!("   Tier: {}", tier);
+    msg!("   By: {}", ctx.accounts.verifier.key());
+    Ok(())
+}
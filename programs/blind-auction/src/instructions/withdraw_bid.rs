@@ -1,11 +1,9 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{self, accounts::VerifySignature},
-    program::IncoLightning,
-    ID as INCO_LIGHTNING_ID,
-};
+use inco_lightning::{program::IncoLightning, ID as INCO_LIGHTNING_ID};
+use crate::compute::{ConfidentialBackend, IncoBackend};
 use crate::state::{Auction, Bid};
 use crate::error::AuctionError;
+use crate::vault::{bps_share, transfer_from_vault};
 
 #[derive(Accounts)]
 pub struct WithdrawBid<'info> {
@@ -15,7 +13,7 @@ pub struct WithdrawBid<'info> {
     #[account(mut)]
     pub auction: Account<'info, Auction>,
 
-    #[account(mut)]
+    #[account(mut, has_one = auction)]
     pub bid: Account<'info, Bid>,
 
     /// CHECK: vault PDA
@@ -36,104 +34,131 @@ pub struct WithdrawBid<'info> {
     pub inco_lightning_program: Program<'info, IncoLightning>,
 }
 
-/// Withdraw funds: winner pays (bid stays in vault), losers get refund (their deposit back)
+/// Withdraw funds: winners pay (payment stays in vault), losers get refund (their deposit back)
 pub fn handler(
     ctx: Context<WithdrawBid>,
     is_winner_handle: Vec<u8>,
     is_winner_plaintext: Vec<u8>,
 ) -> Result<()> {
     let bid = &mut ctx.accounts.bid;
-    let auction = &ctx.accounts.auction;
+    let auction = &mut ctx.accounts.auction;
 
     require!(bid.bidder == ctx.accounts.bidder.key(), AuctionError::NotBidder);
     require!(bid.checked, AuctionError::NotChecked);
     require!(!bid.withdrawn, AuctionError::AlreadyWithdrawn);
     require!(auction.is_closed, AuctionError::AuctionNotClosed);
+    // Bidders of a frozen auction reclaim their deposit with `refund_bid` instead
+    require!(!auction.frozen, AuctionError::AuctionFrozen);
+    // Only the bid's own comparison result decides the payout
+    require!(
+        is_winner_handle == bid.is_winner_handle.to_le_bytes(),
+        AuctionError::WinnerHandleMismatch
+    );
 
     // Verify the decryption signature on-chain for is_winner
-    let cpi_ctx = CpiContext::new(
+    let mut backend = IncoBackend::new(
         ctx.accounts.inco_lightning_program.to_account_info(),
-        VerifySignature {
-            instructions: ctx.accounts.instructions.to_account_info(),
-            signer: ctx.accounts.bidder.to_account_info(),
-        },
-    );
-
-    // Verify signature for is_winner
-    cpi::is_validsignature(
-        cpi_ctx,
-        1,
-        Some(vec![is_winner_handle.clone()]),
-        Some(vec![is_winner_plaintext.clone()]),
-    )?;
+        ctx.accounts.bidder.to_account_info(),
+    )
+    .with_instructions(ctx.accounts.instructions.to_account_info());
+    backend.verify(vec![is_winner_handle], vec![is_winner_plaintext.clone()])?;
 
     // Parse the verified plaintext
     let is_winner = parse_plaintext_to_bool(&is_winner_plaintext)?;
 
     msg!("Verified is_winner: {}", is_winner);
 
+    // Bids tied at the lowest winning amount can outnumber the units. Once every unit
+    // has a confirmed winner, later tied winners are refunded like losers.
+    let is_winner = is_winner && (auction.claim_expired || auction.winning_bids.len() < auction.units as usize);
+
+    // Winners pay the auction price out of their deposit; losers get it all back.
+    // Once the claim period has expired, unconfirmed winners are no-shows and only
+    // forfeit the penalty.
+    let no_show = is_winner && auction.claim_expired;
+    let price = if no_show {
+        bps_share(bid.deposit_amount, auction.no_show_penalty_bps)?
+    } else if is_winner {
+        auction.winner_price(bid.deposit_amount)?
+    } else {
+        0
+    };
+
     bid.withdrawn = true;
 
     // Calculate transfer amount
     let transfer_amount = if is_winner {
-        // Winner pays - their bid amount stays in vault, they get nothing back
-        0
+        // Pay-as-bid winners leave their whole deposit; uniform winners and no-shows get the rest back
+        bid.deposit_amount - price
     } else {
         // Loser gets their full deposit back (refund)
         require!(bid.deposit_amount > 0, AuctionError::NoFunds);
         bid.deposit_amount
     };
 
-    // Only transfer if there's something to transfer (losers get refund)
+    if is_winner {
+        // Payment (or no-show penalty) stays in vault until the seller settles
+        auction.proceeds = auction
+            .proceeds
+            .checked_add(price)
+            .ok_or(AuctionError::MathOverflow)?;
+        auction.settled = false;
+        bid.paid = price;
+    }
+
+    if no_show {
+        msg!("   ⌛ Claim period expired before you confirmed.");
+        msg!("   No-show penalty ({}) remains in vault.", price);
+    } else if is_winner {
+        // Record the winner so the seller knows whom to deliver to
+        bid.won = true;
+        auction.winning_bids.push(bid.key());
+
+        msg!("   🎉 Congratulations! You won the auction!");
+        msg!("   Your payment ({}) remains in vault.", price);
+
+        // Physical items: hold the payment until the buyer confirms delivery or the timeout passes
+        if auction.delivery_timeout > 0 && price > 0 {
+            let release_at = Clock::get()?
+                .unix_timestamp
+                .checked_add(auction.delivery_timeout)
+                .ok_or(AuctionError::MathOverflow)?;
+            bid.escrow_amount = price;
+            bid.escrow_release_at = release_at;
+            auction.escrowed_proceeds = auction
+                .escrowed_proceeds
+                .checked_add(price)
+                .ok_or(AuctionError::MathOverflow)?;
+            msg!("   📦 Held in escrow until you confirm delivery (released at {} otherwise).", release_at);
+        } else {
+            bid.payout_mark = auction.payout_mark()?;
+        }
+    }
+
+    // Only transfer if there's something to transfer (losers and uniform-price winners)
     if transfer_amount > 0 {
-        let auction_key = auction.key();
-        let vault_seeds: &[&[u8]] = &[
-            b"vault",
-            auction_key.as_ref(),
-            &[ctx.bumps.vault],
-        ];
-
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                &ctx.accounts.vault.key(),
-                &ctx.accounts.bidder.key(),
-                transfer_amount,
-            ),
-            &[
-                ctx.accounts.vault.to_account_info(),
-                ctx.accounts.bidder.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[vault_seeds],
+        transfer_from_vault(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.bidder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &auction.key(),
+            ctx.bumps.vault,
+            transfer_amount,
         )?;
 
         msg!("Funds withdrawn: {} lamports!", transfer_amount);
-        msg!("   💰 Your bid has been refunded.");
-    } else {
-        // Winner - their payment stays in vault
-        msg!("   🎉 Congratulations! You won the auction!");
-        msg!("   Your bid amount ({}) remains in vault as payment.", bid.deposit_amount);
+        if !is_winner {
+            msg!("   💰 Your bid has been refunded.");
+        }
     }
     Ok(())
 }
 
-/// Parse decrypted boolean plaintext
-fn parse_plaintext_to_bool(plaintext: &[u8]) -> Result<bool> {
-    if plaintext.is_empty() {
-        return Ok(false);
+/// Parse decrypted boolean plaintext, which the attested-decrypt SDK renders as "1" or "0"
+pub(crate) fn parse_plaintext_to_bool(plaintext: &[u8]) -> Result<bool> {
+    match plaintext {
+        b"1" => Ok(true),
+        b"0" => Ok(false),
+        _ => err!(AuctionError::InvalidInput),
     }
-
-    let any_nonzero = plaintext.iter().any(|&b| b != 0 && b != b'0');
-
-    if let Ok(s) = std::str::from_utf8(plaintext) {
-        if s == "0" || s == "false" {
-            return Ok(false);
-        }
-        if s == "1" || s == "true" {
-            return Ok(true);
-        }
-    }
-
-    Ok(any_nonzero)
 }
-
@@ -2,12 +2,17 @@
 
 use anchor_lang::prelude::*;
 
+pub mod access;
+pub mod bidding;
+pub mod compute;
 pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
+pub mod vault;
 
 use instructions::*;
+use state::{ConfigParams, CreateAuctionParams, ReportReason};
 
 declare_id!("GzVHoPfCw5gW2YN3hqGHg6pkgp7ygcNyhu3mjTqzMBRv"); 
 
@@ -15,6 +20,7 @@ declare_id!("GzVHoPfCw5gW2YN3hqGHg6pkgp7ygcNyhu3mjTqzMBRv");
 pub mod blind_auction {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_auction(
         ctx: Context<CreateAuction>, 
         auction_id: u64, 
@@ -24,20 +30,22 @@ pub mod blind_auction {
         description: String,
         category: String,
         image_url: String,
-        tags: Vec<String>
+        tags: Vec<String>,
+        params: CreateAuctionParams,
     ) -> Result<()> {
-        instructions::create_auction::handler(ctx, auction_id, minimum_bid, end_time, title, description, category, image_url, tags)
+        instructions::create_auction::handler(ctx, auction_id, minimum_bid, end_time, title, description, category, image_url, tags, params)
     }
 
     pub fn place_bid<'info>(
         ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
         encrypted_bid_amount: Vec<u8>,
         deposit_amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        instructions::place_bid::handler(ctx, encrypted_bid_amount, deposit_amount)
+        instructions::place_bid::handler(ctx, encrypted_bid_amount, deposit_amount, merkle_proof)
     }
 
-    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+    pub fn close_auction<'info>(ctx: Context<'_, '_, '_, 'info, CloseAuction<'info>>) -> Result<()> {
         instructions::close_auction::handler(ctx)
     }
 
@@ -57,11 +65,159 @@ pub mod blind_auction {
         instructions::withdraw_bid::handler(ctx, is_winner_handle, is_winner_plaintext)
     }
 
-    pub fn add_comment(
-        ctx: Context<AddComment>,
-        comment_id: u64,
-        comment_text: String,
+    pub fn add_comment(ctx: Context<AddComment>, comment_text: String) -> Result<()> {
+        instructions::add_comment::handler(ctx, comment_text)
+    }
+
+    pub fn edit_comment(ctx: Context<EditComment>, comment_text: String) -> Result<()> {
+        instructions::edit_comment::handler(ctx, comment_text)
+    }
+
+    pub fn delete_comment(ctx: Context<DeleteComment>) -> Result<()> {
+        instructions::delete_comment::handler(ctx)
+    }
+
+    pub fn mark_answer(ctx: Context<MarkAnswer>) -> Result<()> {
+        instructions::mark_answer::handler(ctx)
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::initialize_config::handler(ctx, params)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_admin::handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        instructions::settle_auction::handler(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    pub fn create_allowlist(ctx: Context<CreateAllowlist>, bidders: Vec<Pubkey>) -> Result<()> {
+        instructions::create_allowlist::handler(ctx, bidders)
+    }
+
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, bidders: Vec<Pubkey>) -> Result<()> {
+        instructions::add_to_allowlist::handler(ctx, bidders)
+    }
+
+    pub fn reveal_clearing_price(
+        ctx: Context<RevealClearingPrice>,
+        clearing_price_handle: Vec<u8>,
+        clearing_price_plaintext: Vec<u8>,
+    ) -> Result<()> {
+        instructions::reveal_clearing_price::handler(ctx, clearing_price_handle, clearing_price_plaintext)
+    }
+
+    pub fn publish_result(
+        ctx: Context<PublishResult>,
+        highest_bid_handle: Vec<u8>,
+        highest_bid_plaintext: Vec<u8>,
+    ) -> Result<()> {
+        instructions::publish_result::handler(ctx, highest_bid_handle, highest_bid_plaintext)
+    }
+
+    pub fn expire_claim(ctx: Context<ExpireClaim>) -> Result<()> {
+        instructions::expire_claim::handler(ctx)
+    }
+
+    pub fn forfeit_bid(
+        ctx: Context<ForfeitBid>,
+        is_winner_handle: Vec<u8>,
+        is_winner_plaintext: Vec<u8>,
+    ) -> Result<()> {
+        instructions::forfeit_bid::handler(ctx, is_winner_handle, is_winner_plaintext)
+    }
+
+    pub fn confirm_delivery(ctx: Context<ConfirmDelivery>) -> Result<()> {
+        instructions::confirm_delivery::handler(ctx)
+    }
+
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        instructions::release_escrow::handler(ctx)
+    }
+
+    pub fn create_seller_profile(
+        ctx: Context<CreateSellerProfile>,
+        display_name: String,
+        bio: String,
+        avatar_url: String,
+    ) -> Result<()> {
+        instructions::create_seller_profile::handler(ctx, display_name, bio, avatar_url)
+    }
+
+    pub fn update_seller_profile(
+        ctx: Context<UpdateSellerProfile>,
+        display_name: String,
+        bio: String,
+        avatar_url: String,
+    ) -> Result<()> {
+        instructions::update_seller_profile::handler(ctx, display_name, bio, avatar_url)
+    }
+
+    pub fn add_review(ctx: Context<AddReview>, score: u8, text: String) -> Result<()> {
+        instructions::add_review::handler(ctx, score, text)
+    }
+
+    pub fn verify_seller(ctx: Context<VerifySeller>, tier: u8) -> Result<()> {
+        instructions::verify_seller::handler(ctx, tier)
+    }
+
+    pub fn revoke_seller(ctx: Context<RevokeSeller>) -> Result<()> {
+        instructions::revoke_seller::handler(ctx)
+    }
+
+    pub fn report_auction(ctx: Context<ReportAuction>, reason: ReportReason) -> Result<()> {
+        instructions::report_auction::handler(ctx, reason)
+    }
+
+    pub fn report_comment(ctx: Context<ReportComment>, reason: ReportReason) -> Result<()> {
+        instructions::report_comment::handler(ctx, reason)
+    }
+
+    pub fn moderate_auction(ctx: Context<ModerateAuction>, hidden: bool, frozen: bool) -> Result<()> {
+        instructions::moderate_auction::handler(ctx, hidden, frozen)
+    }
+
+    pub fn refund_bid(ctx: Context<RefundBid>) -> Result<()> {
+        instructions::refund_bid::handler(ctx)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        instructions::cancel_auction::handler(ctx)
+    }
+
+    pub fn update_auction_metadata(
+        ctx: Context<UpdateAuctionMetadata>,
+        title: String,
+        description: String,
+        image_url: String,
+        tags: Vec<String>,
     ) -> Result<()> {
-        instructions::add_comment::handler(ctx, comment_id, comment_text)
+        instructions::update_auction_metadata::handler(ctx, title, description, image_url, tags)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>, evidence: String) -> Result<()> {
+        instructions::open_dispute::handler(ctx, evidence)
+    }
+
+    pub fn add_evidence(ctx: Context<AddEvidence>, evidence: String) -> Result<()> {
+        instructions::add_evidence::handler(ctx, evidence)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, buyer_bps: u16) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, buyer_bps)
     }
 }
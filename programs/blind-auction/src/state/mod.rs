@@ -1,4 +1,229 @@
 use anchor_lang::prelude::*;
+use crate::bidding;
+use crate::constants::{BPS_DENOMINATOR, MAX_ALLOWLIST_SIZE, MAX_CATEGORIES, MAX_CATEGORY_LEN, MAX_CLAIM_PERIOD, MAX_DELIVERY_TIMEOUT, MAX_EVIDENCE, MAX_EVIDENCE_LEN, MAX_FEE_BPS, MAX_HISTOGRAM_BUCKETS, MAX_INSTRUCTION_TRACE, MAX_UNITS};
+use crate::error::AuctionError;
+
+/// Global program configuration (PDA: ["config"])
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,          // Proposed admin, default while no transfer is pending
+    pub guardian: Pubkey,               // May pause (but not unpause) the program
+    pub fee_bps: u16,                   // Protocol fee on seller proceeds (basis points)
+    pub fee_recipient: Pubkey,
+    pub min_minimum_bid: u64,           // Lowest minimum bid an auction may set (lamports)
+    pub max_duration: i64,              // Longest allowed auction in seconds (0 = unlimited)
+    pub claim_period: i64,              // Seconds winners have to confirm after close (0 = no deadline)
+    pub no_show_penalty_bps: u16,       // Share of a no-show winner's deposit kept for the seller
+    pub delivery_timeout: i64,          // Seconds escrowed payments wait for delivery confirmation (0 = escrow unavailable)
+    pub arbiter: Pubkey,                // Default dispute arbiter for escrowed auctions (default = none)
+    pub verifier: Pubkey,               // May verify sellers besides the admin (default = none)
+    pub moderator: Pubkey,              // May hide or freeze flagged auctions besides the admin (default = none)
+    pub allowed_categories: Vec<String>, // Empty list allows any category
+    pub verified_categories: Vec<VerifiedCategory>, // Categories only verified sellers may list in
+    pub paused: bool,                   // Blocks new auctions and bids, never withdrawals
+    pub bump: u8,
+}
+
+impl Config {
+    // 8 (discriminator) + 32 (admin) + 32 (pending_admin) + 32 (guardian) + 2 (fee_bps) + 32 (fee_recipient)
+    // + 8 (min_minimum_bid) + 8 (max_duration) + 8 (claim_period) + 2 (no_show_penalty_bps)
+    // + 8 (delivery_timeout) + 32 (arbiter) + 32 (verifier) + 32 (moderator) + 4+20*(4+50) (allowed_categories)
+    // + 4+20*(4+50+1) (verified_categories) + 1 (paused) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 2 + 32 + 8 + 8 + 8 + 2 + 8 + 32 + 32 + 32
+        + 4 + MAX_CATEGORIES * (4 + MAX_CATEGORY_LEN)
+        + 4 + MAX_CATEGORIES * (4 + MAX_CATEGORY_LEN + 1)
+        + 1 + 1;
+
+    pub fn allows_category(&self, category: &str) -> bool {
+        self.allowed_categories.is_empty() || self.allowed_categories.iter().any(|c| c == category)
+    }
+
+    /// Verification tier a seller needs to list in `category` (0 = none)
+    pub fn required_tier(&self, category: &str) -> u8 {
+        self.verified_categories
+            .iter()
+            .find(|c| c.category == category)
+            .map_or(0, |c| c.min_tier)
+    }
+
+    /// The admin and the verifier may verify sellers
+    pub fn can_verify(&self, signer: &Pubkey) -> bool {
+        *signer == self.admin || (self.verifier != Pubkey::default() && *signer == self.verifier)
+    }
+
+    /// The admin and the moderator may hide or freeze auctions
+    pub fn can_moderate(&self, signer: &Pubkey) -> bool {
+        *signer == self.admin || (self.moderator != Pubkey::default() && *signer == self.moderator)
+    }
+
+    pub fn apply(&mut self, params: ConfigParams) {
+        self.guardian = params.guardian;
+        self.fee_bps = params.fee_bps;
+        self.fee_recipient = params.fee_recipient;
+        self.min_minimum_bid = params.min_minimum_bid;
+        self.max_duration = params.max_duration;
+        self.claim_period = params.claim_period;
+        self.no_show_penalty_bps = params.no_show_penalty_bps;
+        self.delivery_timeout = params.delivery_timeout;
+        self.arbiter = params.arbiter;
+        self.verifier = params.verifier;
+        self.moderator = params.moderator;
+        self.allowed_categories = params.allowed_categories;
+        self.verified_categories = params.verified_categories;
+    }
+}
+
+/// Admin-settable fields of [`Config`]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfigParams {
+    pub guardian: Pubkey,
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub min_minimum_bid: u64,
+    pub max_duration: i64,
+    pub claim_period: i64,
+    pub no_show_penalty_bps: u16,
+    pub delivery_timeout: i64,
+    pub arbiter: Pubkey,
+    pub verifier: Pubkey,
+    pub moderator: Pubkey,
+    pub allowed_categories: Vec<String>,
+    pub verified_categories: Vec<VerifiedCategory>,
+}
+
+impl ConfigParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.fee_bps <= MAX_FEE_BPS, AuctionError::FeeTooHigh);
+        require!(self.max_duration >= 0, AuctionError::InvalidInput);
+        require!((0..=MAX_CLAIM_PERIOD).contains(&self.claim_period), AuctionError::InvalidInput);
+        require!(self.no_show_penalty_bps as u64 <= BPS_DENOMINATOR, AuctionError::InvalidInput);
+        require!((0..=MAX_DELIVERY_TIMEOUT).contains(&self.delivery_timeout), AuctionError::InvalidInput);
+        require!(self.allowed_categories.len() <= MAX_CATEGORIES, AuctionError::InvalidInput);
+        for category in &self.allowed_categories {
+            require!(category.len() <= MAX_CATEGORY_LEN, AuctionError::InvalidInput);
+        }
+        require!(self.verified_categories.len() <= MAX_CATEGORIES, AuctionError::InvalidInput);
+        for requirement in &self.verified_categories {
+            require!(requirement.category.len() <= MAX_CATEGORY_LEN, AuctionError::InvalidInput);
+            require!(requirement.min_tier > 0, AuctionError::InvalidInput);
+        }
+        Ok(())
+    }
+}
+
+/// Minimum seller verification tier for a category
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct VerifiedCategory {
+    pub category: String,
+    pub min_tier: u8,
+}
+
+/// Who may bid on an auction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BidderAccess {
+    /// Any wallet
+    Open,
+    /// Wallets listed in the auction's [`Allowlist`] account
+    Allowlist,
+    /// Wallets with a proof against this root (see `access::merkle_root`)
+    MerkleRoot([u8; 32]),
+}
+
+/// What a token gate checks the bidder's token account against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenGateKind {
+    /// Tokens of this mint
+    Mint,
+    /// NFTs whose metadata has this verified collection
+    Collection,
+}
+
+/// Holding requirement for bidders
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TokenGate {
+    pub kind: TokenGateKind,
+    pub key: Pubkey,                    // Mint or collection mint
+    pub min_amount: u64,                // Minimum balance of the presented token account (base units)
+}
+
+/// What each winner of a multi-unit auction pays
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PricingMode {
+    /// Discriminatory: each winner pays their own deposit
+    PayAsBid,
+    /// Every winner pays the highest losing bid (the minimum bid if all bids win)
+    Uniform,
+}
+
+/// Who may comment on an auction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommentPolicy {
+    /// Anyone, at any time
+    Open,
+    /// Wallets that have bid, plus the seller
+    BiddersOnly,
+    /// Nobody
+    Disabled,
+    /// Anyone until the auction closes
+    LockedAfterClose,
+}
+
+/// Optional settings of a new [`Auction`]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreateAuctionParams {
+    pub access: BidderAccess,
+    pub token_gate: Option<TokenGate>,
+    pub units: u16,
+    pub pricing: PricingMode,
+    pub histogram_buckets: u8,
+    pub public_result: bool,
+    pub delivery_escrow: bool,
+    pub arbiter: Option<Pubkey>,
+    pub comment_policy: CommentPolicy,
+    pub comment_interval: i64,
+}
+
+/// Open single-unit pay-as-bid auction with every extra off
+impl Default for CreateAuctionParams {
+    fn default() -> Self {
+        Self {
+            access: BidderAccess::Open,
+            token_gate: None,
+            units: 1,
+            pricing: PricingMode::PayAsBid,
+            histogram_buckets: 0,
+            public_result: false,
+            delivery_escrow: false,
+            arbiter: None,
+            comment_policy: CommentPolicy::Open,
+            comment_interval: 0,
+        }
+    }
+}
+
+impl CreateAuctionParams {
+    pub fn validate(&self, minimum_bid: u64) -> Result<()> {
+        if let Some(gate) = &self.token_gate {
+            require!(gate.min_amount > 0, AuctionError::InvalidInput);
+        }
+        require!(self.comment_interval >= 0, AuctionError::InvalidInput);
+        require!((1..=MAX_UNITS).contains(&self.units), AuctionError::InvalidUnits);
+        if self.histogram_buckets > 0 {
+            require!(
+                self.histogram_buckets <= MAX_HISTOGRAM_BUCKETS && minimum_bid > 0,
+                AuctionError::InvalidHistogram
+            );
+        }
+        // Every bid updates each top bid slot and histogram bucket in the same transaction
+        let capacity = Auction::top_bid_capacity_for(self.units, self.pricing);
+        require!(
+            bidding::place_bid_trace_length(capacity, self.histogram_buckets as usize) <= MAX_INSTRUCTION_TRACE,
+            AuctionError::TooManyBidOperations
+        );
+        Ok(())
+    }
+}
 
 /// Auction account
 #[account]
@@ -13,6 +238,44 @@ pub struct Auction {
     pub highest_bid_handle: u128,        // Encrypted highest bid amount
     pub winner_determined: bool,         // Has winner been determined?
     pub bump: u8, 
+    // Settlement
+    pub fee_bps: u16,                   // Protocol fee snapshotted from config at creation
+    pub proceeds: u64,                  // Winner payment held in vault for the seller
+    pub settled: bool,                  // Proceeds paid out to seller and fee recipient
+    pub settled_proceeds: u64,          // Proceeds already paid out (winners of other units may still add more)
+    pub cancelled: bool,                // Withdrawn by the seller before any bids
+    pub access: BidderAccess,           // Who may bid
+    pub token_gate: Option<TokenGate>,  // Tokens a bidder must hold
+    // Multi-unit
+    pub units: u16,                     // Identical units for sale, one per winning bid
+    pub pricing: PricingMode,           // What winners pay
+    pub top_bid_handles: Vec<u128>,     // Encrypted top bids, highest first
+    pub clearing_price: Option<u64>,    // Uniform price once known
+    pub bid_histogram: Vec<u128>,       // Encrypted count of bids at or above each multiple of minimum_bid
+    pub histogram_one_handle: u128,     // Encrypted 1 the histogram counts bids with (0 until the first bid)
+    pub histogram_zero_handle: u128,    // Encrypted 0 added for bids below a bucket (0 until the first bid)
+    // Result
+    pub public_result: bool,            // Seller publishes the winning bid after close
+    pub winning_bid: Option<u64>,       // Verified highest bid, once published
+    pub winning_bids: Vec<Pubkey>,      // Bid accounts of winners who confirmed, for delivery
+    // Claims
+    pub claim_period: i64,              // Snapshotted from config at creation
+    pub no_show_penalty_bps: u16,       // Snapshotted from config at creation
+    pub claim_deadline: i64,            // Winners confirm by this time, set at close (0 = no deadline)
+    pub claim_expired: bool,            // Unconfirmed winners now reclaim their deposit minus the penalty
+    // Delivery escrow
+    pub delivery_timeout: i64,          // Snapshotted from config when the seller opts in (0 = no escrow)
+    pub escrowed_proceeds: u64,         // Winner payments held until delivery is confirmed or times out
+    pub arbiter: Pubkey,                // Rules on disputes over escrowed payments (default = none)
+    // Moderation
+    pub flag_count: u32,                // Reports filed against this auction
+    pub hidden: bool,                   // Hidden from listings by a moderator
+    pub frozen: bool,                   // Frozen by a moderator: no bids or payouts, every bidder can reclaim their deposit
+    pub refunded: bool,                 // A deposit was refunded while frozen, so the freeze can't be lifted
+    // Comments
+    pub comment_count: u64,             // Comments posted so far; the next comment's id
+    pub comment_policy: CommentPolicy,  // Who may comment
+    pub comment_interval: i64,          // Seconds a commenter waits between comments (0 = no limit)
     // Metadata fields     
     pub title: String,                  // Auction title (max 100 chars)
     pub description: String,            // Auction description (max 1000 chars)
@@ -25,9 +288,179 @@ impl Auction {
     // Base size: 8 (discriminator) + 32 (authority) + 8 (auction_id) + 8 (minimum_bid) + 8 (end_time) 
     // + 4 (bidder_count) + 1 (is_open) + 1 (is_closed) + 16 (highest_bid_handle) + 1 (winner_determined) 
     // + 1 (bump) = 87 bytes
-    // Metadata: 4+100 (title) + 4+1000 (description) + 4+50 (category) + 4+200 (image_url) 
-    // + 4+10*(4+30) (tags) = 104 + 1004 + 54 + 204 + 344 = 1710 bytes
-    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 4 + 1 + 1 + 16 + 1 + 1 + 4 + 100 + 4 + 1000 + 4 + 50 + 4 + 200 + 4 + 10 * (4 + 30);
+    // Settlement: 2 (fee_bps) + 8 (proceeds) + 1 (settled) + 8 (settled_proceeds) + 1 (cancelled) = 20 bytes
+    // Access: 1 (variant) + 32 (merkle root) = 33 bytes
+    // Token gate: 1 (option) + 1 (kind) + 32 (key) + 8 (min_amount) = 42 bytes
+    // Multi-unit: 2 (units) + 1 (pricing) + 4 (top_bid_handles) + 1+8 (clearing_price)
+    // Histogram: 4 (bid_histogram) + 16 (histogram_one_handle) + 16 (histogram_zero_handle)
+    // Result: 1 (public_result) + 1+8 (winning_bid) + 4 (winning_bids)
+    // Claims: 8 (claim_period) + 2 (no_show_penalty_bps) + 8 (claim_deadline) + 1 (claim_expired)
+    // Delivery escrow: 8 (delivery_timeout) + 8 (escrowed_proceeds) + 32 (arbiter)
+    // Moderation: 4 (flag_count) + 1 (hidden) + 1 (frozen) + 1 (refunded)
+    // Comments: 8 (comment_count) + 1 (comment_policy) + 8 (comment_interval)
+    // Metadata: 4 (title) + 4 (description) + 4 (category) + 4 (image_url) + 4 (tags) length prefixes
+    // Vec contents are added by `lists_space` and `space`
+    pub const BASE_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 4 + 1 + 1 + 16 + 1 + 1 + 2 + 8 + 1 + 8 + 1 + 33 + 42 + 2 + 1 + 4 + 9 + 4 + 16 + 16 + 1 + 9 + 4 + 8 + 2 + 8 + 1 + 8 + 8 + 32 + 4 + 1 + 1 + 1 + 8 + 1 + 8 + 4 + 4 + 4 + 4 + 4;
+
+    /// Room for every encrypted top bid, histogram bucket and confirmed winner of an
+    /// auction with these settings. It is fixed at creation.
+    pub fn lists_space(units: u16, pricing: PricingMode, histogram_buckets: u8) -> usize {
+        Self::top_bid_capacity_for(units, pricing) * 16 + histogram_buckets as usize * 16 + units as usize * 32
+    }
+
+    /// `lists_space` this auction was created with
+    pub fn allocated_lists_space(&self) -> usize {
+        Self::lists_space(self.units, self.pricing, self.bid_histogram.len() as u8)
+    }
+
+    /// Account size for these lists and metadata. Only metadata edits resize the account.
+    pub fn space(lists_space: usize, title: &str, description: &str, category: &str, image_url: &str, tags: &[String]) -> usize {
+        Self::BASE_SIZE
+            + lists_space
+            + title.len()
+            + description.len()
+            + category.len()
+            + image_url.len()
+            + tags.iter().map(|tag| 4 + tag.len()).sum::<usize>()
+    }
+
+    pub fn validate_metadata(title: &str, description: &str, category: &str, image_url: &str, tags: &[String]) -> Result<()> {
+        require!(title.len() <= 100, AuctionError::InvalidInput);
+        require!(description.len() <= 1000, AuctionError::InvalidInput);
+        require!(category.len() <= 50, AuctionError::InvalidInput);
+        require!(image_url.len() <= 200, AuctionError::InvalidInput);
+        require!(tags.len() <= 10, AuctionError::InvalidInput);
+        for tag in tags {
+            require!(tag.len() <= 30, AuctionError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Bids tracked in `top_bid_handles`: one per unit, plus the highest
+    /// losing bid when it sets a uniform price
+    pub fn top_bid_capacity(&self) -> usize {
+        Self::top_bid_capacity_for(self.units, self.pricing)
+    }
+
+    fn top_bid_capacity_for(units: u16, pricing: PricingMode) -> usize {
+        match pricing {
+            PricingMode::PayAsBid => units as usize,
+            PricingMode::Uniform => units as usize + 1,
+        }
+    }
+
+    /// Lowest winning bid: a bid wins a unit if it is at least this high.
+    /// With fewer bids than units this is the lowest bid, so every bid wins.
+    pub fn winning_threshold_handle(&self) -> u128 {
+        let winners = self.top_bid_handles.len().min(self.units as usize);
+        self.top_bid_handles[..winners].last().copied().unwrap_or(0)
+    }
+
+    /// Highest losing bid, which sets the uniform price. `None` for
+    /// pay-as-bid auctions and when every bid wins.
+    pub fn clearing_price_handle(&self) -> Option<u128> {
+        match self.pricing {
+            PricingMode::PayAsBid => None,
+            PricingMode::Uniform => self.top_bid_handles.get(self.units as usize).copied(),
+        }
+    }
+
+    /// Handles the seller may decrypt once the auction closes, in the order
+    /// `close_auction` expects their allowance accounts
+    pub fn seller_handles(&self) -> Vec<u128> {
+        self.clearing_price_handle()
+            .into_iter()
+            .chain(std::iter::once(self.highest_bid_handle))
+            .chain(self.bid_histogram.iter().copied())
+            .collect()
+    }
+
+    /// Lowest bid counted by each histogram bucket
+    pub fn histogram_thresholds(&self) -> impl Iterator<Item = u64> + '_ {
+        (1..=self.bid_histogram.len() as u64).map(|i| i * self.minimum_bid)
+    }
+
+    /// What a winner with `deposit` in the vault pays. Uniform winners never
+    /// pay more than they deposited.
+    pub fn winner_price(&self, deposit: u64) -> Result<u64> {
+        match self.pricing {
+            PricingMode::PayAsBid => Ok(deposit),
+            PricingMode::Uniform => {
+                let price = self.clearing_price.ok_or(AuctionError::ClearingPriceNotRevealed)?;
+                Ok(price.min(deposit))
+            }
+        }
+    }
+
+    /// Move a winner's escrowed payment into the proceeds the seller can settle
+    pub fn release_escrow(&mut self, bid: &mut Bid) -> Result<u64> {
+        let amount = bid.escrow_amount;
+        require!(amount > 0, AuctionError::NothingInEscrow);
+        require!(!bid.disputed, AuctionError::EscrowDisputed);
+        self.escrowed_proceeds = self
+            .escrowed_proceeds
+            .checked_sub(amount)
+            .ok_or(AuctionError::MathOverflow)?;
+        self.settled = false;
+        bid.escrow_amount = 0;
+        bid.payout_mark = self.payout_mark()?;
+        Ok(amount)
+    }
+
+    /// `settled_proceeds` once everything the seller can settle now has been paid out.
+    /// Settleable proceeds only grow until the auction is frozen, so a payment recorded
+    /// with this mark has been paid out once `settled_proceeds` reaches it.
+    pub fn payout_mark(&self) -> Result<u64> {
+        self.proceeds
+            .checked_sub(self.escrowed_proceeds)
+            .ok_or_else(|| error!(AuctionError::MathOverflow))
+    }
+
+    /// Whether the payment behind `bid` has been paid out to the seller
+    pub fn is_payment_settled(&self, bid: &Bid) -> bool {
+        bid.paid > 0 && bid.payout_mark > 0 && self.settled_proceeds >= bid.payout_mark
+    }
+
+    /// Take a winner's payment (or a no-show's penalty) back out of the proceeds of a
+    /// frozen auction. Payments already settled to the seller can't be returned.
+    pub fn refund_payment(&mut self, bid: &mut Bid) -> Result<u64> {
+        let amount = bid.paid;
+        require!(amount > 0, AuctionError::AlreadyWithdrawn);
+        require!(!bid.disputed, AuctionError::EscrowDisputed);
+        let unsettled = self
+            .proceeds
+            .checked_sub(self.settled_proceeds)
+            .and_then(|p| p.checked_sub(self.escrowed_proceeds))
+            .ok_or(AuctionError::MathOverflow)?;
+        require!(amount - bid.escrow_amount <= unsettled, AuctionError::PaymentSettled);
+
+        self.escrowed_proceeds = self
+            .escrowed_proceeds
+            .checked_sub(bid.escrow_amount)
+            .ok_or(AuctionError::MathOverflow)?;
+        self.proceeds = self
+            .proceeds
+            .checked_sub(amount)
+            .ok_or(AuctionError::MathOverflow)?;
+        bid.escrow_amount = 0;
+        bid.paid = 0;
+        Ok(amount)
+    }
+
+    /// The seller and the winner behind `bid` may dispute its escrowed payment
+    pub fn is_dispute_party(&self, bid: &Bid, key: &Pubkey) -> bool {
+        *key == self.authority || *key == bid.bidder
+    }
+
+    /// Comments can be posted or edited: not cancelled, disabled, or locked by the close
+    pub fn require_comments_open(&self) -> Result<()> {
+        require!(!self.cancelled, AuctionError::CommentsLocked);
+        match self.comment_policy {
+            CommentPolicy::Disabled => err!(AuctionError::CommentsDisabled),
+            CommentPolicy::LockedAfterClose if self.is_closed => err!(AuctionError::CommentsLocked),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Bid account - stores each bidder's encrypted bid
@@ -41,11 +474,111 @@ pub struct Bid {
     pub refund_amount_handle: u128,       // Encrypted refund (0 for winner, bid amount for losers)
     pub checked: bool,                    // Whether winner status has been checked
     pub withdrawn: bool,                 // Whether funds have been withdrawn
+    pub won: bool,                       // Verified winner at withdraw
+    pub paid: u64,                       // Winner payment or no-show penalty kept in the vault, escrow included
+    pub payout_mark: u64,                // Auction's payout mark once the payment left escrow (0 before)
+    pub escrow_amount: u64,              // Payment awaiting delivery confirmation
+    pub escrow_release_at: i64,          // Seller may release the escrow from this time without confirmation
+    pub disputed: bool,                  // Escrow frozen until the arbiter rules
+    pub dispute_refund: Option<u64>,     // Escrow the arbiter's ruling returned to the buyer
     pub bump: u8,
 }
 
 impl Bid {
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 16 + 16 + 16 + 1 + 1 + 1 + 32;
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 16 + 16 + 16 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 1 + 9 + 1 + 32;
+}
+
+/// Dispute over a winner's escrowed payment (PDA: ["dispute", bid]). Keyed by bid rather
+/// than auction because a multi-unit auction pays one escrow per winner, and each of them
+/// may be disputed on its own. Closed to whoever opened it once the arbiter rules, which
+/// leaves the ruling recorded on the bid.
+#[account]
+pub struct Dispute {
+    pub auction: Pubkey,
+    pub bid: Pubkey,                     // Winning bid whose escrow is frozen
+    pub opened_by: Pubkey,               // Seller or buyer
+    pub amount: u64,                     // Escrowed payment under dispute
+    pub evidence: Vec<String>,           // References to off-chain evidence (URLs, content hashes)
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+impl Dispute {
+    // 8 (discriminator) + 32 (auction) + 32 (bid) + 32 (opened_by) + 8 (amount)
+    // + 4+8*(4+200) (evidence) + 8 (opened_at) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 4 + MAX_EVIDENCE * (4 + MAX_EVIDENCE_LEN) + 8 + 1;
+
+    pub fn add_evidence(&mut self, reference: String) -> Result<()> {
+        require!(!reference.is_empty() && reference.len() <= MAX_EVIDENCE_LEN, AuctionError::InvalidInput);
+        require!(self.evidence.len() < MAX_EVIDENCE, AuctionError::TooMuchEvidence);
+        self.evidence.push(reference);
+        Ok(())
+    }
+}
+
+/// Seller profile (PDA: ["seller", authority]), created with the seller's first auction if not before
+#[account]
+pub struct SellerProfile {
+    pub authority: Pubkey,
+    pub display_name: String,            // Display name (max 50 chars)
+    pub bio: String,                     // Bio (max 500 chars)
+    pub avatar_url: String,              // Avatar URL (max 200 chars)
+    pub auctions_created: u32,
+    pub auctions_settled: u32,           // Auctions with at least one settlement
+    pub auctions_cancelled: u32,
+    pub rating_count: u32,               // Reviews received
+    pub rating_total: u64,               // Sum of review scores; average = rating_total / rating_count
+    pub verification_tier: u8,           // Set by the admin or verifier (0 = unverified)
+    pub verified_at: i64,                // When the tier was last set
+    pub bump: u8,
+}
+
+impl SellerProfile {
+    // 8 (discriminator) + 32 (authority) + 4+50 (display_name) + 4+500 (bio) + 4+200 (avatar_url)
+    // + 4 (auctions_created) + 4 (auctions_settled) + 4 (auctions_cancelled)
+    // + 4 (rating_count) + 8 (rating_total) + 1 (verification_tier) + 8 (verified_at) + 1 (bump) = 836 bytes
+    pub const SIZE: usize = 8 + 32 + 4 + 50 + 4 + 500 + 4 + 200 + 4 + 4 + 4 + 4 + 8 + 1 + 8 + 1;
+
+    pub fn set_details(&mut self, display_name: String, bio: String, avatar_url: String) -> Result<()> {
+        require!(display_name.len() <= 50, AuctionError::InvalidInput);
+        require!(bio.len() <= 500, AuctionError::InvalidInput);
+        require!(avatar_url.len() <= 200, AuctionError::InvalidInput);
+        self.display_name = display_name;
+        self.bio = bio;
+        self.avatar_url = avatar_url;
+        Ok(())
+    }
+}
+
+/// Review of a settled auction by its winner (PDA: ["review", auction])
+#[account]
+pub struct Review {
+    pub auction: Pubkey,
+    pub seller: Pubkey,
+    pub reviewer: Pubkey,
+    pub score: u8,                       // 1-5
+    pub text: String,                    // Review text (max 500 chars)
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl Review {
+    // 8 (discriminator) + 32 (auction) + 32 (seller) + 32 (reviewer) + 1 (score) + 4+500 (text)
+    // + 8 (timestamp) + 1 (bump) = 618 bytes
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 1 + 4 + 500 + 8 + 1;
+}
+
+/// Allowlist account - wallets invited to an auction with `BidderAccess::Allowlist`
+#[account]
+pub struct Allowlist {
+    pub auction: Pubkey,
+    pub bidders: Vec<Pubkey>,            // Max MAX_ALLOWLIST_SIZE entries
+    pub bump: u8,
+}
+
+impl Allowlist {
+    // 8 (discriminator) + 32 (auction) + 4+100*32 (bidders) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 4 + MAX_ALLOWLIST_SIZE * 32 + 1;
 }
 
 /// Comment account - stores comments on auctions
@@ -53,12 +586,83 @@ impl Bid {
 pub struct Comment {
     pub auction: Pubkey,
     pub commenter: Pubkey,
+    pub comment_id: u64,               // Assigned from the auction's comment_count, part of the seed
     pub comment: String,              // Comment text (max 500 chars)
     pub timestamp: i64,                // Unix timestamp when comment was created
+    pub edited_at: i64,                // Unix timestamp of the last edit (0 = never edited)
+    pub flag_count: u32,               // Reports filed against this comment
+    pub parent: Option<Pubkey>,        // Comment this one replies to
+    pub reply_count: u32,              // Replies currently posted under this comment
+    pub answer: Option<Pubkey>,        // Reply the auction authority marked as the official answer
     pub bump: u8,
 }
 
 impl Comment {
-    // Base size: 8 (discriminator) + 32 (auction) + 32 (commenter) + 4+500 (comment) + 8 (timestamp) + 1 (bump) = 585 bytes
-    pub const SIZE: usize = 8 + 32 + 32 + 4 + 500 + 8 + 1;
+    // Base size: 8 (discriminator) + 32 (auction) + 32 (commenter) + 8 (comment_id) + 4 (comment prefix)
+    // + 8 (timestamp) + 8 (edited_at) + 4 (flag_count) + 1+32 (parent) + 4 (reply_count) + 1+32 (answer)
+    // + 1 (bump) = 175 bytes, plus the text
+    pub const BASE_SIZE: usize = 8 + 32 + 32 + 8 + 4 + 8 + 8 + 4 + 33 + 4 + 33 + 1;
+
+    /// Account size for a comment with this text
+    pub fn space(comment_text: &str) -> usize {
+        Self::BASE_SIZE + comment_text.len()
+    }
+}
+
+/// When a commenter last commented on an auction, for its `comment_interval`
+#[account]
+pub struct CommentRecord {
+    pub auction: Pubkey,
+    pub commenter: Pubkey,
+    pub last_comment_at: i64,
+    pub bump: u8,
+}
+
+impl CommentRecord {
+    // 8 (discriminator) + 32 (auction) + 32 (commenter) + 8 (last_comment_at) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// What a [`Report`] is about
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportTarget {
+    Auction,
+    Comment,
+}
+
+/// Why something was reported
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportReason {
+    Spam,
+    Fraud,
+    ProhibitedItem,
+    Offensive,
+    Other,
+}
+
+/// A user's report of an auction or comment (PDA: ["report", target, reporter]),
+/// so each reporter flags a target at most once
+#[account]
+pub struct Report {
+    pub target: Pubkey,                  // Reported auction or comment
+    pub kind: ReportTarget,
+    pub reporter: Pubkey,
+    pub reason: ReportReason,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl Report {
+    // 8 (discriminator) + 32 (target) + 1 (kind) + 32 (reporter) + 1 (reason) + 8 (timestamp) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 1 + 32 + 1 + 8 + 1;
+
+    pub fn init(&mut self, target: Pubkey, kind: ReportTarget, reporter: Pubkey, reason: ReportReason, bump: u8) -> Result<()> {
+        self.target = target;
+        self.kind = kind;
+        self.reporter = reporter;
+        self.reason = reason;
+        self.timestamp = Clock::get()?.unix_timestamp;
+        self.bump = bump;
+        Ok(())
+    }
 }
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::AuctionError;
+
+/// Transfer lamports out of an auction's vault PDA (seeds: ["vault", auction])
+pub fn transfer_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    auction: &Pubkey,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let vault_seeds: &[&[u8]] = &[b"vault", auction.as_ref(), &[vault_bump]];
+    transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Transfer { from: vault.clone(), to: to.clone() },
+            &[vault_seeds],
+        ),
+        amount,
+    )
+}
+
+/// `bps` basis points of `amount`, rounded down
+pub fn bps_share(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(AuctionError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(share).map_err(|_| error!(AuctionError::MathOverflow))
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use blind_auction::{
+    access::{merkle_proof, merkle_root},
+    error::AuctionError,
+    state::BidderAccess,
+};
+use common::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer};
+
+const AUCTION_ID: u64 = 3;
+
+#[test]
+fn allowlist_auction_only_accepts_listed_bidders() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction_with_access(AUCTION_ID, MINIMUM_BID, end_time, BidderAccess::Allowlist).unwrap();
+
+    let partner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let late_partner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL);
+
+    // No allowlist account yet
+    assert_auction_error(env.place_bid(&partner, AUCTION_ID, MINIMUM_BID, MINIMUM_BID), AuctionError::MissingAccount);
+
+    env.create_allowlist(AUCTION_ID, vec![partner.pubkey()]).unwrap();
+    env.place_bid(&partner, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    assert_auction_error(
+        env.place_bid(&outsider, AUCTION_ID, MINIMUM_BID, MINIMUM_BID),
+        AuctionError::NotAllowlisted,
+    );
+
+    assert_auction_error(
+        env.add_to_allowlist(&outsider, AUCTION_ID, vec![outsider.pubkey()]),
+        AuctionError::Unauthorized,
+    );
+    let authority = env.authority.insecure_clone();
+    env.add_to_allowlist(&authority, AUCTION_ID, vec![late_partner.pubkey()]).unwrap();
+    env.place_bid(&late_partner, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).bidder_count, 2);
+}
+
+#[test]
+fn allowlist_account_requires_allowlist_access() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    assert_auction_error(
+        env.create_allowlist(AUCTION_ID, vec![Pubkey::new_unique()]),
+        AuctionError::AllowlistNotEnabled,
+    );
+}
+
+#[test]
+fn merkle_auction_requires_a_valid_proof() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let partners: Vec<_> = (0..3).map(|_| env.funded_keypair(LAMPORTS_PER_SOL)).collect();
+    let members: Vec<Pubkey> = partners.iter().map(|k| k.pubkey()).collect();
+    let root = merkle_root(&members);
+    env.create_auction_with_access(AUCTION_ID, MINIMUM_BID, end_time, BidderAccess::MerkleRoot(root)).unwrap();
+
+    let outsider = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(
+        env.place_bid_with_proof(&outsider, AUCTION_ID, MINIMUM_BID, merkle_proof(&members, 0)),
+        AuctionError::NotAllowlisted,
+    );
+    assert_auction_error(
+        env.place_bid_with_proof(&partners[1], AUCTION_ID, MINIMUM_BID, vec![]),
+        AuctionError::NotAllowlisted,
+    );
+
+    for (i, partner) in partners.iter().enumerate() {
+        env.place_bid_with_proof(partner, AUCTION_ID, MINIMUM_BID, merkle_proof(&members, i)).unwrap();
+    }
+    assert_eq!(env.auction(AUCTION_ID).bidder_count, 3);
+}
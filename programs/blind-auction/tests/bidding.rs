@@ -0,0 +1,147 @@
+//! Core bidding logic against the plaintext simulator, no SVM required.
+
+use blind_auction::bidding;
+use blind_auction::compute::{simulator::PlaintextSimulator, ConfidentialBackend};
+use proptest::prelude::*;
+
+/// Place `amounts` into a top-`capacity` list, returning bid handles and the list
+fn place_all_top(sim: &mut PlaintextSimulator, amounts: &[u64], capacity: usize) -> (Vec<u128>, Vec<u128>) {
+    let mut top = Vec::new();
+    let mut handles = Vec::new();
+    for &amount in amounts {
+        let handle = sim.new_euint128(PlaintextSimulator::encrypt(amount as u128)).unwrap();
+        bidding::insert_top_bid(sim, &mut top, capacity, handle).unwrap();
+        handles.push(handle.0);
+    }
+    (handles, top)
+}
+
+fn place_all(sim: &mut PlaintextSimulator, amounts: &[u64]) -> (Vec<u128>, u128) {
+    let mut highest = 0;
+    let mut handles = Vec::new();
+    for &amount in amounts {
+        let handle = sim.new_euint128(PlaintextSimulator::encrypt(amount as u128)).unwrap();
+        highest = bidding::update_highest_bid(sim, highest, handle).unwrap();
+        handles.push(handle.0);
+    }
+    (handles, highest)
+}
+
+#[test]
+fn first_bid_becomes_highest() {
+    let mut sim = PlaintextSimulator::new();
+    let (handles, highest) = place_all(&mut sim, &[42]);
+    assert_eq!(highest, handles[0]);
+}
+
+#[test]
+fn tied_bids_all_reach_the_threshold() {
+    let mut sim = PlaintextSimulator::new();
+    let (handles, highest) = place_all(&mut sim, &[50, 50, 10]);
+
+    let winners: Vec<u128> = handles
+        .iter()
+        .map(|&h| {
+            let is_winner = bidding::is_winning_bid(&mut sim, h, highest).unwrap();
+            sim.decrypt(is_winner.0).unwrap()
+        })
+        .collect();
+    assert_eq!(winners, vec![1, 1, 0]);
+}
+
+#[test]
+fn verify_rejects_wrong_plaintext() {
+    let mut sim = PlaintextSimulator::new();
+    let (handles, highest) = place_all(&mut sim, &[10, 20]);
+    let is_winner = bidding::is_winning_bid(&mut sim, handles[0], highest).unwrap();
+
+    let handle = is_winner.0.to_le_bytes().to_vec();
+    assert!(sim.verify(vec![handle.clone()], vec![b"1".to_vec()]).is_err());
+    assert!(sim.verify(vec![handle], vec![b"0".to_vec()]).is_ok());
+}
+
+#[test]
+fn top_bids_keep_the_highest_in_order() {
+    let mut sim = PlaintextSimulator::new();
+    let (_, top) = place_all_top(&mut sim, &[30, 70, 10, 50, 90], 3);
+    let values: Vec<u128> = top.iter().map(|&h| sim.decrypt(h).unwrap()).collect();
+    assert_eq!(values, vec![90, 70, 50]);
+}
+
+proptest! {
+    #[test]
+    fn highest_is_maximum(amounts in prop::collection::vec(1u64..1_000_000_000, 1..20)) {
+        let mut sim = PlaintextSimulator::new();
+        let (_, highest) = place_all(&mut sim, &amounts);
+        prop_assert_eq!(sim.decrypt(highest), amounts.iter().max().map(|&m| m as u128));
+    }
+
+    #[test]
+    fn distinct_bids_have_exactly_one_winner(amounts in prop::collection::hash_set(1u64..1_000_000_000, 1..20)) {
+        let amounts: Vec<u64> = amounts.into_iter().collect();
+        let mut sim = PlaintextSimulator::new();
+        let (handles, highest) = place_all(&mut sim, &amounts);
+
+        let mut winners = Vec::new();
+        for (i, &handle) in handles.iter().enumerate() {
+            let is_winner = bidding::is_winning_bid(&mut sim, handle, highest).unwrap();
+            if sim.decrypt(is_winner.0) == Some(1) {
+                winners.push(amounts[i]);
+            }
+        }
+        prop_assert_eq!(winners, vec![*amounts.iter().max().unwrap()]);
+    }
+
+    #[test]
+    fn top_bids_match_sorted_amounts(
+        amounts in prop::collection::vec(1u64..1_000_000_000, 1..20),
+        capacity in 1usize..8,
+    ) {
+        let mut sim = PlaintextSimulator::new();
+        let (_, top) = place_all_top(&mut sim, &amounts, capacity);
+
+        let mut expected: Vec<u128> = amounts.iter().map(|&a| a as u128).collect();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        expected.truncate(capacity);
+        let values: Vec<u128> = top.iter().map(|&h| sim.decrypt(h).unwrap()).collect();
+        prop_assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn distinct_bids_have_exactly_k_winners(
+        amounts in prop::collection::hash_set(1u64..1_000_000_000, 1..20),
+        units in 1usize..6,
+    ) {
+        let amounts: Vec<u64> = amounts.into_iter().collect();
+        let mut sim = PlaintextSimulator::new();
+        let (handles, top) = place_all_top(&mut sim, &amounts, units);
+        let threshold = *top.last().unwrap();
+
+        let mut winners = 0;
+        for &handle in &handles {
+            let is_winner = bidding::is_winning_bid(&mut sim, handle, threshold).unwrap();
+            winners += sim.decrypt(is_winner.0).unwrap();
+        }
+        prop_assert_eq!(winners as usize, units.min(amounts.len()));
+    }
+
+    #[test]
+    fn histogram_counts_bids_at_or_above_each_step(
+        amounts in prop::collection::vec(0u64..100, 1..20),
+        buckets in 1usize..8,
+    ) {
+        let mut sim = PlaintextSimulator::new();
+        let mut histogram = vec![0; buckets];
+        let (mut one, mut zero) = (0, 0);
+        for &amount in &amounts {
+            let handle = sim.new_euint128(PlaintextSimulator::encrypt(amount as u128)).unwrap();
+            bidding::record_in_histogram(&mut sim, &mut histogram, 10, &mut one, &mut zero, handle).unwrap();
+        }
+
+        for (i, &count) in histogram.iter().enumerate() {
+            let threshold = 10 * (i as u64 + 1);
+            let expected = amounts.iter().filter(|&&a| a >= threshold).count() as u128;
+            prop_assert_eq!(sim.decrypt(count), Some(expected));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use blind_auction::{
+    error::AuctionError,
+    state::{ConfigParams, PricingMode},
+};
+use common::*;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+
+const AUCTION_ID: u64 = 1;
+const CLAIM_PERIOD: i64 = 3_600;
+const PENALTY_BPS: u16 = 5_000;
+
+/// Closed auction with a claim period and no-show penalty, and a winning and a losing bid
+fn claimable_auction(env: &mut TestEnv) -> [Keypair; 2] {
+    let configure = |params: &mut ConfigParams| {
+        params.claim_period = CLAIM_PERIOD;
+        params.no_show_penalty_bps = PENALTY_BPS;
+    };
+    closed_auction(env, AUCTION_ID, configure, AuctionOptions::default(), [80_000_000, 20_000_000])
+}
+
+#[test]
+fn no_show_winner_reclaims_deposit_minus_penalty() {
+    let mut env = TestEnv::new();
+    let [winner, loser] = claimable_auction(&mut env);
+    let deadline = env.auction(AUCTION_ID).claim_deadline;
+    assert_eq!(deadline, env.now() + CLAIM_PERIOD);
+
+    assert_auction_error(env.expire_claim(AUCTION_ID), AuctionError::ClaimPeriodNotOver);
+    env.warp_to(deadline);
+    env.expire_claim(AUCTION_ID).unwrap();
+    assert!(env.auction(AUCTION_ID).claim_expired);
+    assert_auction_error(env.expire_claim(AUCTION_ID), AuctionError::ClaimAlreadyExpired);
+
+    for bidder in [&winner, &loser] {
+        env.check_win(bidder, AUCTION_ID).unwrap();
+    }
+    let before = env.balance(&winner.pubkey());
+    for bidder in [&winner, &loser] {
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+    let penalty = 80_000_000 * PENALTY_BPS as u64 / 10_000;
+    let fee_margin = 10_000;
+    assert!(env.balance(&winner.pubkey()) + fee_margin >= before + 80_000_000 - penalty);
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.proceeds, penalty);
+    assert!(auction.winning_bids.is_empty());
+    assert!(!env.bid(AUCTION_ID, &winner.pubkey()).won);
+    env.settle_auction(AUCTION_ID).unwrap();
+}
+
+#[test]
+fn silent_winner_forfeits_the_penalty_without_responding() {
+    let mut env = TestEnv::new();
+    let [winner, loser] = claimable_auction(&mut env);
+    let seller = env.authority.insecure_clone();
+    env.determine_winner(&seller, AUCTION_ID, &winner.pubkey()).unwrap();
+    assert_auction_error(env.forfeit_bid(&seller, AUCTION_ID, &winner.pubkey()), AuctionError::ClaimPeriodNotOver);
+
+    env.warp_to(env.auction(AUCTION_ID).claim_deadline);
+    env.expire_claim(AUCTION_ID).unwrap();
+    let stranger = env.funded_keypair(LAMPORTS_PER_SOL);
+    for bidder in [&winner, &loser] {
+        env.determine_winner(&stranger, AUCTION_ID, &bidder.pubkey()).unwrap();
+    }
+    assert_auction_error(env.forfeit_bid(&stranger, AUCTION_ID, &loser.pubkey()), AuctionError::NotWinner);
+
+    let before = env.balance(&winner.pubkey());
+    env.forfeit_bid(&stranger, AUCTION_ID, &winner.pubkey()).unwrap();
+    let penalty = 80_000_000 * PENALTY_BPS as u64 / 10_000;
+    assert_eq!(env.balance(&winner.pubkey()), before + 80_000_000 - penalty);
+    assert_eq!(env.auction(AUCTION_ID).proceeds, penalty);
+    assert!(env.bid(AUCTION_ID, &winner.pubkey()).withdrawn);
+    assert_auction_error(env.forfeit_bid(&stranger, AUCTION_ID, &winner.pubkey()), AuctionError::AlreadyWithdrawn);
+
+    // The loser still reclaims the full deposit, and the seller collects the penalty
+    env.withdraw_bid(&loser, AUCTION_ID).unwrap();
+    env.settle_auction(AUCTION_ID).unwrap();
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 0);
+}
+
+#[test]
+fn checked_winner_keeps_their_result_past_the_deadline() {
+    let mut env = TestEnv::new();
+    let [winner, _] = claimable_auction(&mut env);
+    env.check_win(&winner, AUCTION_ID).unwrap();
+    let handle = env.bid(AUCTION_ID, &winner.pubkey()).is_winner_handle;
+
+    // Anyone may be shown the result once the claim expires, but it is not recomputed
+    env.warp_to(env.auction(AUCTION_ID).claim_deadline);
+    env.expire_claim(AUCTION_ID).unwrap();
+    let stranger = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.determine_winner(&stranger, AUCTION_ID, &winner.pubkey()).unwrap();
+    assert_eq!(env.bid(AUCTION_ID, &winner.pubkey()).is_winner_handle, handle);
+    assert!(env.svm.get_account(&allowance_pda(handle, &stranger.pubkey())).is_some());
+
+    env.withdraw_bid(&winner, AUCTION_ID).unwrap();
+    assert_auction_error(env.forfeit_bid(&stranger, AUCTION_ID, &winner.pubkey()), AuctionError::AlreadyWithdrawn);
+}
+
+#[test]
+fn confirmed_winners_cannot_be_expired() {
+    let mut env = TestEnv::new();
+    let [winner, _] = claimable_auction(&mut env);
+    env.check_win(&winner, AUCTION_ID).unwrap();
+    env.withdraw_bid(&winner, AUCTION_ID).unwrap();
+
+    let deadline = env.auction(AUCTION_ID).claim_deadline;
+    env.warp_to(deadline);
+    assert_auction_error(env.expire_claim(AUCTION_ID), AuctionError::AllWinnersConfirmed);
+}
+
+#[test]
+fn auctions_without_claim_period_never_expire() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+
+    assert_eq!(env.auction(AUCTION_ID).claim_deadline, 0);
+    assert_auction_error(env.expire_claim(AUCTION_ID), AuctionError::NoClaimDeadline);
+}
+
+#[test]
+fn uniform_claims_expire_only_after_the_price_is_revealed() {
+    let mut env = TestEnv::new();
+    let options = AuctionOptions { pricing: PricingMode::Uniform, ..AuctionOptions::default() };
+    let configure = |params: &mut ConfigParams| params.claim_period = CLAIM_PERIOD;
+    closed_auction(&mut env, AUCTION_ID, configure, options, [80_000_000, 20_000_000]);
+
+    // Winners can't confirm without a price, so they can't be no-shows yet
+    env.warp_to(env.auction(AUCTION_ID).claim_deadline);
+    assert_auction_error(env.expire_claim(AUCTION_ID), AuctionError::ClearingPriceNotRevealed);
+
+    env.reveal_clearing_price(AUCTION_ID).unwrap();
+    env.expire_claim(AUCTION_ID).unwrap();
+    assert!(env.auction(AUCTION_ID).claim_expired);
+}
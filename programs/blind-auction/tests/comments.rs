@@ -0,0 +1,219 @@
+mod common;
+
+use blind_auction::{
+    error::AuctionError,
+    state::{Comment, CommentPolicy, CommentRecord},
+};
+use common::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+const AUCTION_ID: u64 = 1;
+
+#[test]
+fn comments_get_sequential_ids() {
+    let mut env = TestEnv::new();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let first = env.funded_keypair(LAMPORTS_PER_SOL);
+    let second = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&first, AUCTION_ID, "First!").unwrap();
+    env.add_comment(&second, AUCTION_ID, "Second").unwrap();
+    env.delete_comment(&first, AUCTION_ID, 0, &first.pubkey()).unwrap();
+    env.add_comment(&first, AUCTION_ID, "Third").unwrap();
+
+    // Deleted ids are never reused
+    assert_eq!(env.auction(AUCTION_ID).comment_count, 3);
+    for (id, text) in [(1, "Second"), (2, "Third")] {
+        let comment = env.comment(AUCTION_ID, id);
+        assert_eq!(comment.comment_id, id);
+        assert_eq!(comment.comment, text);
+    }
+}
+
+#[test]
+fn author_can_edit_a_comment() {
+    let mut env = TestEnv::new();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&commenter, AUCTION_ID, "Is it still in teh box?").unwrap();
+    assert_eq!(env.comment(AUCTION_ID, 0).edited_at, 0);
+
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.edit_comment(&intruder, AUCTION_ID, 0, "Scam!"), AuctionError::Unauthorized);
+    assert_auction_error(env.edit_comment(&commenter, AUCTION_ID, 0, ""), AuctionError::InvalidInput);
+
+    env.edit_comment(&commenter, AUCTION_ID, 0, "Is it still in the box?").unwrap();
+    let comment = env.comment(AUCTION_ID, 0);
+    assert_eq!(comment.comment, "Is it still in the box?");
+    assert_eq!(comment.edited_at, env.now());
+}
+
+#[test]
+fn comment_accounts_are_sized_to_their_text() {
+    let mut env = TestEnv::new();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&commenter, AUCTION_ID, "Nice!").unwrap();
+    let address = comment_pda(&auction_pda(AUCTION_ID), 0);
+    assert_eq!(env.account_len(&address), Comment::space("Nice!"));
+
+    // Growing the text charges the commenter more rent, shrinking it refunds them
+    let long = "Nice! ".repeat(50);
+    let before = env.balance(&commenter.pubkey());
+    env.edit_comment(&commenter, AUCTION_ID, 0, &long).unwrap();
+    assert_eq!(env.account_len(&address), Comment::space(&long));
+    assert!(env.balance(&commenter.pubkey()) < before);
+
+    let before = env.balance(&commenter.pubkey());
+    env.edit_comment(&commenter, AUCTION_ID, 0, "Nice").unwrap();
+    assert_eq!(env.account_len(&address), Comment::space("Nice"));
+    assert!(env.balance(&commenter.pubkey()) > before);
+    assert_eq!(env.comment(AUCTION_ID, 0).comment, "Nice");
+}
+
+#[test]
+fn author_seller_or_moderator_can_delete_a_comment() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let authority = env.authority.insecure_clone();
+    let moderator = env.funded_keypair(LAMPORTS_PER_SOL);
+    let mut params = default_config_params(&env);
+    params.moderator = moderator.pubkey();
+    env.update_config(&admin, params).unwrap();
+
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    for _ in 0..3 {
+        env.add_comment(&commenter, AUCTION_ID, "Free SOL at example.com").unwrap();
+    }
+
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let result = env.delete_comment(&intruder, AUCTION_ID, 0, &commenter.pubkey());
+    assert_auction_error(result, AuctionError::Unauthorized);
+    // Rent can only go back to the author
+    assert!(env.delete_comment(&authority, AUCTION_ID, 0, &intruder.pubkey()).is_err());
+
+    let auction = auction_pda(AUCTION_ID);
+    for (id, signer) in [(0, &commenter), (1, &authority), (2, &moderator)] {
+        let before = env.balance(&commenter.pubkey());
+        env.delete_comment(signer, AUCTION_ID, id, &commenter.pubkey()).unwrap();
+        assert_eq!(env.balance(&comment_pda(&auction, id)), 0);
+        assert!(env.balance(&commenter.pubkey()) > before);
+    }
+}
+
+#[test]
+fn replies_are_counted_and_the_seller_marks_an_answer() {
+    let mut env = TestEnv::new();
+    let authority = env.authority.insecure_clone();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let asker = env.funded_keypair(LAMPORTS_PER_SOL);
+    let helper = env.funded_keypair(LAMPORTS_PER_SOL);
+    let auction = auction_pda(AUCTION_ID);
+    let question = comment_pda(&auction, 0);
+    env.add_comment(&asker, AUCTION_ID, "Does it ship internationally?").unwrap();
+    env.add_reply(&helper, AUCTION_ID, Some(question), "I think so").unwrap();
+    env.add_reply(&authority, AUCTION_ID, Some(question), "Yes, worldwide").unwrap();
+
+    assert_eq!(env.comment(AUCTION_ID, 0).parent, None);
+    assert_eq!(env.comment(AUCTION_ID, 0).reply_count, 2);
+    assert_eq!(env.comment(AUCTION_ID, 2).parent, Some(question));
+
+    // Only the auction authority marks answers, and only replies to that question
+    assert_auction_error(env.mark_answer(&asker, AUCTION_ID, 0, 2), AuctionError::Unauthorized);
+    assert_auction_error(env.mark_answer(&authority, AUCTION_ID, 1, 2), AuctionError::NotAReply);
+    env.mark_answer(&authority, AUCTION_ID, 0, 2).unwrap();
+    assert_eq!(env.comment(AUCTION_ID, 0).answer, Some(comment_pda(&auction, 2)));
+
+    // Deleting the answer clears it and updates the count
+    env.delete_comment(&authority, AUCTION_ID, 2, &authority.pubkey()).unwrap();
+    let comment = env.comment(AUCTION_ID, 0);
+    assert_eq!(comment.reply_count, 1);
+    assert_eq!(comment.answer, None);
+
+    // Replies outlive their parent and can still be deleted
+    env.delete_comment(&asker, AUCTION_ID, 0, &asker.pubkey()).unwrap();
+    env.delete_comment(&helper, AUCTION_ID, 1, &helper.pubkey()).unwrap();
+}
+
+#[test]
+fn replies_stay_on_the_same_auction() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    env.create_auction(AUCTION_ID + 1, MINIMUM_BID, end_time).unwrap();
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&commenter, AUCTION_ID, "Question").unwrap();
+
+    let other = Some(comment_pda(&auction_pda(AUCTION_ID), 0));
+    let result = env.add_reply(&commenter, AUCTION_ID + 1, other, "Reply");
+    assert_auction_error(result, AuctionError::ParentMismatch);
+}
+
+fn create_with_comments(env: &mut TestEnv, comment_policy: CommentPolicy, comment_interval: i64) -> i64 {
+    let end_time = env.now() + 60;
+    let options = AuctionOptions { comment_policy, comment_interval, ..AuctionOptions::default() };
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options).unwrap();
+    end_time
+}
+
+#[test]
+fn comment_policy_limits_who_can_comment() {
+    let mut env = TestEnv::new();
+    create_with_comments(&mut env, CommentPolicy::Disabled, 0);
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.add_comment(&commenter, AUCTION_ID, "Hello"), AuctionError::CommentsDisabled);
+
+    let mut env = TestEnv::new();
+    let authority = env.authority.insecure_clone();
+    create_with_comments(&mut env, CommentPolicy::BiddersOnly, 0);
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let lurker = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    assert_auction_error(env.add_comment(&lurker, AUCTION_ID, "Hello"), AuctionError::CommenterNotBidder);
+    env.add_comment(&bidder, AUCTION_ID, "When does it ship?").unwrap();
+    env.add_comment(&authority, AUCTION_ID, "Within a week").unwrap();
+}
+
+#[test]
+fn comments_lock_when_the_auction_closes_or_is_cancelled() {
+    let mut env = TestEnv::new();
+    let end_time = create_with_comments(&mut env, CommentPolicy::LockedAfterClose, 0);
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&commenter, AUCTION_ID, "Before close").unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    assert_auction_error(env.add_comment(&commenter, AUCTION_ID, "After close"), AuctionError::CommentsLocked);
+    let result = env.edit_comment(&commenter, AUCTION_ID, 0, "Edited after close");
+    assert_auction_error(result, AuctionError::CommentsLocked);
+
+    // Cancelled auctions take no comments whatever their policy
+    let mut env = TestEnv::new();
+    create_with_comments(&mut env, CommentPolicy::Open, 0);
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&commenter, AUCTION_ID, "Before cancel").unwrap();
+    env.cancel_auction(AUCTION_ID).unwrap();
+    assert_auction_error(env.add_comment(&commenter, AUCTION_ID, "Hello"), AuctionError::CommentsLocked);
+    let result = env.edit_comment(&commenter, AUCTION_ID, 0, "Edited after cancel");
+    assert_auction_error(result, AuctionError::CommentsLocked);
+}
+
+#[test]
+fn commenters_wait_the_comment_interval() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let options = AuctionOptions { comment_interval: -1, ..AuctionOptions::default() };
+    assert_auction_error(env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options), AuctionError::InvalidInput);
+
+    create_with_comments(&mut env, CommentPolicy::Open, 30);
+    let first = env.funded_keypair(LAMPORTS_PER_SOL);
+    let second = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&first, AUCTION_ID, "One").unwrap();
+    env.add_comment(&second, AUCTION_ID, "Someone else").unwrap();
+    assert_auction_error(env.add_comment(&first, AUCTION_ID, "Two"), AuctionError::CommentTooSoon);
+
+    let record = comment_record_pda(&auction_pda(AUCTION_ID), &first.pubkey());
+    let commented_at = env.account::<CommentRecord>(&record).last_comment_at;
+    env.warp_to(commented_at + 30);
+    env.add_comment(&first, AUCTION_ID, "Two").unwrap();
+    assert_eq!(env.auction(AUCTION_ID).comment_count, 3);
+}
@@ -0,0 +1,1289 @@
+//! Local SVM harness: loads the compiled blind auction program next to the
+//! mock Inco Lightning program and drives it the way a client would.
+//!
+//! Build both programs first:
+//!   anchor build
+//!   cargo build-sbf --manifest-path tests/mock-inco-lightning/Cargo.toml
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use anchor_lang::{
+    error::ERROR_CODE_OFFSET, solana_program::program_pack::Pack, AccountDeserialize, InstructionData,
+    ToAccountMetas,
+};
+use anchor_spl::{
+    metadata::mpl_token_metadata::{accounts::Metadata, types::Key, ID as METADATA_PROGRAM_ID},
+    token::spl_token::state::{Account as SplTokenAccount, AccountState},
+};
+use blind_auction::{
+    accounts, constants::INCO_LIGHTNING_ID, error::AuctionError, instruction,
+    state::{
+        Auction, Bid, BidderAccess, Comment, CommentPolicy, Config, ConfigParams, CreateAuctionParams, Dispute,
+        PricingMode, Report, ReportReason, Review, SellerProfile, TokenGate,
+    },
+};
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use mock_inco_lightning::codec;
+use solana_loader_v3_interface::{get_program_data_address, state::UpgradeableLoaderState};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+
+pub const MINIMUM_BID: u64 = 10_000_000;
+pub const FEE_BPS: u16 = 250;
+pub const DELIVERY_TIMEOUT: i64 = 7 * 24 * 3_600;
+
+/// litesvm's `TransactionResult` with the large failure metadata boxed
+pub type TransactionResult = Result<TransactionMetadata, Box<FailedTransactionMetadata>>;
+
+/// Extra credentials a bidder presents to restricted auctions
+#[derive(Default)]
+pub struct BidCredentials {
+    pub merkle_proof: Vec<[u8; 32]>,
+    pub gate_token_account: Option<Pubkey>,
+    pub gate_metadata: Option<Pubkey>,
+}
+
+/// `create_auction` arguments beyond id, minimum bid and end time
+pub struct AuctionOptions {
+    pub category: String,
+    pub access: BidderAccess,
+    pub token_gate: Option<TokenGate>,
+    pub units: u16,
+    pub pricing: PricingMode,
+    pub histogram_buckets: u8,
+    pub public_result: bool,
+    pub delivery_escrow: bool,
+    pub arbiter: Option<Pubkey>,
+    pub comment_policy: CommentPolicy,
+    pub comment_interval: i64,
+}
+
+impl Default for AuctionOptions {
+    fn default() -> Self {
+        Self {
+            category: "NFTs".to_string(),
+            access: BidderAccess::Open,
+            token_gate: None,
+            units: 1,
+            pricing: PricingMode::PayAsBid,
+            histogram_buckets: 0,
+            public_result: false,
+            delivery_escrow: false,
+            arbiter: None,
+            comment_policy: CommentPolicy::Open,
+            comment_interval: 0,
+        }
+    }
+}
+
+pub struct TestEnv {
+    pub svm: LiteSVM,
+    /// Seller of every auction created through the harness
+    pub authority: Keypair,
+    /// Upgrade authority of the program and initial config admin
+    pub admin: Keypair,
+    pub guardian: Keypair,
+    pub fee_recipient: Pubkey,
+}
+
+impl TestEnv {
+    /// Fresh SVM with an initialized config charging [`FEE_BPS`]
+    pub fn new() -> Self {
+        let mut env = Self::uninitialized();
+        env.initialize_config(default_config_params(&env)).unwrap();
+        env
+    }
+
+    /// Fresh SVM whose config has not been created yet. The seller already has a profile.
+    pub fn uninitialized() -> Self {
+        let mut svm = LiteSVM::new();
+        let admin = Keypair::new();
+        add_upgradeable_program(&mut svm, blind_auction::ID, &admin.pubkey());
+        svm.add_program_from_file(INCO_LIGHTNING_ID, program_path("mock_inco_lightning"))
+            .expect("mock_inco_lightning.so not found, run `cargo build-sbf` in tests/mock-inco-lightning");
+
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+        svm.airdrop(&admin.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        let guardian = Keypair::new();
+        svm.airdrop(&guardian.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let mut env = Self { svm, authority, admin, guardian, fee_recipient: Pubkey::new_unique() };
+        let seller = env.authority.insecure_clone();
+        env.create_seller_profile(&seller, "Test Seller", "").unwrap();
+        env
+    }
+
+    pub fn funded_keypair(&mut self, lamports: u64) -> Keypair {
+        let keypair = Keypair::new();
+        self.svm.airdrop(&keypair.pubkey(), lamports).unwrap();
+        keypair
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or_default()
+    }
+
+    pub fn send(&mut self, ixs: &[Instruction], payer: &Keypair) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&payer.pubkey()),
+            &[payer],
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx).map_err(Box::new);
+        // Let identical instructions be resent later in the same test, and give
+        // the next transaction's encrypted results fresh handles
+        self.svm.expire_blockhash();
+        let slot = self.svm.get_sysvar::<Clock>().slot;
+        self.svm.warp_to_slot(slot + 1);
+        result
+    }
+
+    /// Simulate `ixs` without their allowance accounts and return the handle the
+    /// program logs after `prefix`. The simulation itself fails on the missing
+    /// allowance, but the handle is logged before that check.
+    pub fn simulate_handle(
+        &self,
+        ixs: &[Instruction],
+        payer: &Keypair,
+        prefix: &str,
+    ) -> Result<u128, Box<FailedTransactionMetadata>> {
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&payer.pubkey()),
+            &[payer],
+            self.svm.latest_blockhash(),
+        );
+        let (logs, failure) = match self.svm.simulate_transaction(tx) {
+            Ok(sim) => (sim.meta.logs, None),
+            Err(e) => (e.meta.logs.clone(), Some(Box::new(e))),
+        };
+        let handle = logs
+            .iter()
+            .filter_map(|log| log.split_once(prefix))
+            .find_map(|(_, rest)| rest.trim().parse().ok());
+        match (handle, failure) {
+            (Some(handle), _) => Ok(handle),
+            (None, Some(e)) => Err(e),
+            (None, None) => panic!("no '{}' in logs", prefix),
+        }
+    }
+
+    /// Send `ix` after the mock's attestation that `signer` may decrypt `handle`. Like
+    /// the covalidator, the harness only attests handles the signer holds an allowance on.
+    pub fn send_attested(&mut self, ix: Instruction, handle: &[u8], signer: &Keypair) -> TransactionResult {
+        let mut ixs = Vec::new();
+        if let Ok(handle) = <[u8; 16]>::try_from(handle).map(u128::from_le_bytes) {
+            let allowance = allowance_pda(handle, &signer.pubkey());
+            if self.svm.get_account(&allowance).is_some() {
+                ixs.push(Instruction {
+                    program_id: INCO_LIGHTNING_ID,
+                    accounts: mock_inco_lightning::accounts::AttestDecryption {
+                        allowance_account: allowance,
+                        allowed_address: signer.pubkey(),
+                    }
+                    .to_account_metas(None),
+                    data: mock_inco_lightning::instruction::AttestDecryption { handle }.data(),
+                });
+            }
+        }
+        ixs.push(ix);
+        self.send(&ixs, signer)
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(address).expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn auction(&self, auction_id: u64) -> Auction {
+        self.account(&auction_pda(auction_id))
+    }
+
+    pub fn bid(&self, auction_id: u64, bidder: &Pubkey) -> Bid {
+        self.account(&bid_pda(&auction_pda(auction_id), bidder))
+    }
+
+    pub fn config(&self) -> Config {
+        self.account(&config_pda())
+    }
+
+    pub fn initialize_config(&mut self, params: ConfigParams) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        self.initialize_config_as(&admin, params)
+    }
+
+    pub fn initialize_config_as(&mut self, signer: &Keypair, params: ConfigParams) -> TransactionResult {
+        let ix = ix(
+            accounts::InitializeConfig {
+                admin: signer.pubkey(),
+                config: config_pda(),
+                program: blind_auction::ID,
+                program_data: get_program_data_address(&blind_auction::ID),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeConfig { params },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn set_paused(&mut self, signer: &Keypair, paused: bool) -> TransactionResult {
+        let ix = ix(
+            accounts::SetPaused { signer: signer.pubkey(), config: config_pda() },
+            instruction::SetPaused { paused },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn update_config(&mut self, signer: &Keypair, params: ConfigParams) -> TransactionResult {
+        let ix = ix(
+            accounts::UpdateConfig { admin: signer.pubkey(), config: config_pda() },
+            instruction::UpdateConfig { params },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn transfer_admin(&mut self, signer: &Keypair, new_admin: Pubkey) -> TransactionResult {
+        let ix = ix(
+            accounts::TransferAdmin { admin: signer.pubkey(), config: config_pda() },
+            instruction::TransferAdmin { new_admin },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn accept_admin(&mut self, signer: &Keypair) -> TransactionResult {
+        let ix = ix(
+            accounts::AcceptAdmin { pending_admin: signer.pubkey(), config: config_pda() },
+            instruction::AcceptAdmin {},
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn create_auction(&mut self, auction_id: u64, minimum_bid: u64, end_time: i64) -> TransactionResult {
+        self.create_auction_in(auction_id, minimum_bid, end_time, "NFTs")
+    }
+
+    pub fn create_auction_in(
+        &mut self,
+        auction_id: u64,
+        minimum_bid: u64,
+        end_time: i64,
+        category: &str,
+    ) -> TransactionResult {
+        let options = AuctionOptions { category: category.to_string(), ..AuctionOptions::default() };
+        self.create_auction_with(auction_id, minimum_bid, end_time, options)
+    }
+
+    pub fn create_auction_with_access(
+        &mut self,
+        auction_id: u64,
+        minimum_bid: u64,
+        end_time: i64,
+        access: BidderAccess,
+    ) -> TransactionResult {
+        self.create_auction_with(auction_id, minimum_bid, end_time, AuctionOptions { access, ..AuctionOptions::default() })
+    }
+
+    pub fn create_gated_auction(
+        &mut self,
+        auction_id: u64,
+        minimum_bid: u64,
+        end_time: i64,
+        token_gate: TokenGate,
+    ) -> TransactionResult {
+        let options = AuctionOptions { token_gate: Some(token_gate), ..AuctionOptions::default() };
+        self.create_auction_with(auction_id, minimum_bid, end_time, options)
+    }
+
+    pub fn create_multi_unit_auction(
+        &mut self,
+        auction_id: u64,
+        minimum_bid: u64,
+        end_time: i64,
+        units: u16,
+        pricing: PricingMode,
+    ) -> TransactionResult {
+        self.create_auction_with(auction_id, minimum_bid, end_time, AuctionOptions { units, pricing, ..AuctionOptions::default() })
+    }
+
+    pub fn create_auction_with(
+        &mut self,
+        auction_id: u64,
+        minimum_bid: u64,
+        end_time: i64,
+        options: AuctionOptions,
+    ) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::CreateAuction {
+                authority: self.authority.pubkey(),
+                config: config_pda(),
+                seller_profile: seller_profile_pda(&self.authority.pubkey()),
+                auction,
+                vault: vault_pda(&auction),
+                system_program: system_program::ID,
+            },
+            instruction::CreateAuction {
+                auction_id,
+                minimum_bid,
+                end_time,
+                title: format!("Auction {}", auction_id),
+                description: "Test auction".to_string(),
+                category: options.category,
+                image_url: String::new(),
+                tags: vec!["test".to_string()],
+                params: CreateAuctionParams {
+                    access: options.access,
+                    token_gate: options.token_gate,
+                    units: options.units,
+                    pricing: options.pricing,
+                    histogram_buckets: options.histogram_buckets,
+                    public_result: options.public_result,
+                    delivery_escrow: options.delivery_escrow,
+                    arbiter: options.arbiter,
+                    comment_policy: options.comment_policy,
+                    comment_interval: options.comment_interval,
+                },
+            },
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &authority)
+    }
+
+    pub fn place_bid(&mut self, bidder: &Keypair, auction_id: u64, amount: u64, deposit: u64) -> TransactionResult {
+        self.place_bid_allowed(bidder, auction_id, amount, deposit, BidCredentials::default())
+    }
+
+    pub fn place_bid_with(
+        &mut self,
+        bidder: &Keypair,
+        auction_id: u64,
+        amount: u64,
+        deposit: u64,
+        remaining: Vec<AccountMeta>,
+    ) -> TransactionResult {
+        self.place_bid_full(bidder, auction_id, amount, deposit, BidCredentials::default(), remaining)
+    }
+
+    pub fn place_bid_with_proof(
+        &mut self,
+        bidder: &Keypair,
+        auction_id: u64,
+        amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
+    ) -> TransactionResult {
+        let credentials = BidCredentials { merkle_proof, ..Default::default() };
+        self.place_bid_with_credentials(bidder, auction_id, amount, credentials)
+    }
+
+    pub fn place_bid_with_credentials(
+        &mut self,
+        bidder: &Keypair,
+        auction_id: u64,
+        amount: u64,
+        credentials: BidCredentials,
+    ) -> TransactionResult {
+        self.place_bid_allowed(bidder, auction_id, amount, amount, credentials)
+    }
+
+    /// Bid, passing the bidder's allowance accounts for the handle a simulation returns
+    fn place_bid_allowed(
+        &mut self,
+        bidder: &Keypair,
+        auction_id: u64,
+        amount: u64,
+        deposit: u64,
+        credentials: BidCredentials,
+    ) -> TransactionResult {
+        let mut ix = self.place_bid_ix(bidder, auction_id, amount, deposit, credentials, vec![]);
+        self.allow_bid_handle(&[], &mut ix, bidder)?;
+        self.send(&[ix], bidder)
+    }
+
+    /// Append to `place_bid_ix` the bidder's allowance accounts for the bid handle it
+    /// creates when sent after `preceding`
+    pub fn allow_bid_handle(
+        &self,
+        preceding: &[Instruction],
+        place_bid_ix: &mut Instruction,
+        bidder: &Keypair,
+    ) -> Result<(), Box<FailedTransactionMetadata>> {
+        let ixs = [preceding, std::slice::from_ref(place_bid_ix)].concat();
+        let handle = self.simulate_handle(&ixs, bidder, "Bid handle:")?;
+        place_bid_ix.accounts.extend(allowance_accounts(handle, &bidder.pubkey()));
+        Ok(())
+    }
+
+    fn place_bid_full(
+        &mut self,
+        bidder: &Keypair,
+        auction_id: u64,
+        amount: u64,
+        deposit: u64,
+        credentials: BidCredentials,
+        remaining: Vec<AccountMeta>,
+    ) -> TransactionResult {
+        let ix = self.place_bid_ix(bidder, auction_id, amount, deposit, credentials, remaining);
+        self.send(&[ix], bidder)
+    }
+
+    /// Passes the auction's allowlist account whenever one exists
+    pub fn place_bid_ix(
+        &self,
+        bidder: &Keypair,
+        auction_id: u64,
+        amount: u64,
+        deposit: u64,
+        credentials: BidCredentials,
+        remaining: Vec<AccountMeta>,
+    ) -> Instruction {
+        let auction = auction_pda(auction_id);
+        let allowlist = Some(allowlist_pda(&auction)).filter(|a| self.svm.get_account(a).is_some());
+        ix(
+            accounts::PlaceBid {
+                bidder: bidder.pubkey(),
+                auction,
+                config: config_pda(),
+                allowlist,
+                gate_token_account: credentials.gate_token_account,
+                gate_metadata: credentials.gate_metadata,
+                bid: bid_pda(&auction, &bidder.pubkey()),
+                vault: vault_pda(&auction),
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::PlaceBid {
+                encrypted_bid_amount: codec::encrypt(amount as u128),
+                deposit_amount: deposit,
+                merkle_proof: credentials.merkle_proof,
+            },
+            remaining,
+        )
+    }
+
+    /// Write an SPL token account of `owner` holding `amount` of `mint`
+    pub fn set_token_account(&mut self, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let token_account = SplTokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0; SplTokenAccount::LEN];
+        SplTokenAccount::pack(token_account, &mut data).unwrap();
+        self.set_program_account(address, data, anchor_spl::token::ID);
+        address
+    }
+
+    /// Write the Token Metadata account of `mint`, belonging to `collection`
+    pub fn set_nft_metadata(&mut self, mint: &Pubkey, collection: &Pubkey, verified: bool) -> Pubkey {
+        let (address, _) = Metadata::find_pda(mint);
+        let mut data = vec![Key::MetadataV1 as u8];
+        data.extend_from_slice(self.authority.pubkey().as_ref()); // update_authority
+        data.extend_from_slice(mint.as_ref());
+        for field in ["Test NFT", "TEST", ""] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&0u16.to_le_bytes()); // seller_fee_basis_points
+        data.extend_from_slice(&[0, 0, 1, 0, 0]); // creators, primary_sale_happened, is_mutable, edition_nonce, token_standard
+        data.extend_from_slice(&[1, verified as u8]);
+        data.extend_from_slice(collection.as_ref());
+        data.extend_from_slice(&[0, 0, 0]); // uses, collection_details, programmable_config
+        self.set_program_account(address, data, METADATA_PROGRAM_ID);
+        address
+    }
+
+    fn set_program_account(&mut self, address: Pubkey, data: Vec<u8>, owner: Pubkey) {
+        let lamports = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm.set_account(address, Account { lamports, data, owner, executable: false, rent_epoch: 0 }).unwrap();
+    }
+
+    pub fn create_allowlist(&mut self, auction_id: u64, bidders: Vec<Pubkey>) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::CreateAllowlist {
+                authority: self.authority.pubkey(),
+                auction,
+                allowlist: allowlist_pda(&auction),
+                system_program: system_program::ID,
+            },
+            instruction::CreateAllowlist { bidders },
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &authority)
+    }
+
+    pub fn add_to_allowlist(&mut self, signer: &Keypair, auction_id: u64, bidders: Vec<Pubkey>) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::AddToAllowlist { authority: signer.pubkey(), auction, allowlist: allowlist_pda(&auction) },
+            instruction::AddToAllowlist { bidders },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn close_auction(&mut self, auction_id: u64) -> TransactionResult {
+        let authority = self.authority.insecure_clone();
+        self.close_auction_as(&authority, auction_id)
+    }
+
+    /// Close, passing the signer's allowance accounts for the seller-only handles
+    pub fn close_auction_as(&mut self, signer: &Keypair, auction_id: u64) -> TransactionResult {
+        let remaining = self
+            .auction(auction_id)
+            .seller_handles()
+            .into_iter()
+            .flat_map(|handle| allowance_accounts(handle, &signer.pubkey()))
+            .collect();
+        let ix = ix(
+            accounts::CloseAuction {
+                authority: signer.pubkey(),
+                auction: auction_pda(auction_id),
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::CloseAuction {},
+            remaining,
+        );
+        self.send(&[ix], signer)
+    }
+
+    /// Reveal the clearing price, presenting the mock's decryption of its handle
+    pub fn reveal_clearing_price(&mut self, auction_id: u64) -> TransactionResult {
+        let handle = self.auction(auction_id).clearing_price_handle().expect("no clearing price handle");
+        self.reveal_clearing_price_with(auction_id, codec::handle_bytes(handle), codec::plaintext_bytes(handle))
+    }
+
+    pub fn reveal_clearing_price_with(
+        &mut self,
+        auction_id: u64,
+        clearing_price_handle: Vec<u8>,
+        clearing_price_plaintext: Vec<u8>,
+    ) -> TransactionResult {
+        let ix = ix(
+            accounts::RevealClearingPrice {
+                authority: self.authority.pubkey(),
+                auction: auction_pda(auction_id),
+                instructions: sysvar::instructions::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::RevealClearingPrice { clearing_price_handle: clearing_price_handle.clone(), clearing_price_plaintext },
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send_attested(ix, &clearing_price_handle, &authority)
+    }
+
+    /// Publish the winning bid, presenting the mock's decryption of the highest bid
+    pub fn publish_result(&mut self, auction_id: u64) -> TransactionResult {
+        let handle = self.auction(auction_id).highest_bid_handle;
+        self.publish_result_with(auction_id, codec::handle_bytes(handle), codec::plaintext_bytes(handle))
+    }
+
+    pub fn publish_result_with(
+        &mut self,
+        auction_id: u64,
+        highest_bid_handle: Vec<u8>,
+        highest_bid_plaintext: Vec<u8>,
+    ) -> TransactionResult {
+        let ix = ix(
+            accounts::PublishResult {
+                authority: self.authority.pubkey(),
+                auction: auction_pda(auction_id),
+                instructions: sysvar::instructions::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::PublishResult { highest_bid_handle: highest_bid_handle.clone(), highest_bid_plaintext },
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send_attested(ix, &highest_bid_handle, &authority)
+    }
+
+    pub fn check_win(&mut self, bidder: &Keypair, auction_id: u64) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let check_ix = |remaining| {
+            ix(
+                accounts::CheckWin {
+                    bidder: bidder.pubkey(),
+                    auction,
+                    bid: bid_pda(&auction, &bidder.pubkey()),
+                    system_program: system_program::ID,
+                    inco_lightning_program: INCO_LIGHTNING_ID,
+                },
+                instruction::CheckWin {},
+                remaining,
+            )
+        };
+        let handle = self.simulate_handle(&[check_ix(vec![])], bidder, "Result handle:")?;
+        self.send(&[check_ix(allowance_accounts(handle, &bidder.pubkey()))], bidder)
+    }
+
+    pub fn determine_winner(&mut self, checker: &Keypair, auction_id: u64, bidder: &Pubkey) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let determine_ix = |remaining| {
+            ix(
+                accounts::DetermineWinner {
+                    checker: checker.pubkey(),
+                    auction,
+                    bid: bid_pda(&auction, bidder),
+                    system_program: system_program::ID,
+                    inco_lightning_program: INCO_LIGHTNING_ID,
+                },
+                instruction::DetermineWinner {},
+                remaining,
+            )
+        };
+        let handle = self.simulate_handle(&[determine_ix(vec![])], checker, "Result handle:")?;
+        let mut remaining = allowance_accounts(handle, bidder);
+        // Past the claim deadline the checker is granted the result too
+        let bid = self.bid(auction_id, bidder);
+        if self.auction(auction_id).claim_expired && !bid.withdrawn && checker.pubkey() != *bidder {
+            remaining.extend(allowance_accounts(handle, &checker.pubkey()));
+        }
+        self.send(&[determine_ix(remaining)], checker)
+    }
+
+    /// Withdraw, presenting the mock's decryption of the bid's `is_winner_handle`
+    pub fn withdraw_bid(&mut self, bidder: &Keypair, auction_id: u64) -> TransactionResult {
+        let handle = self.bid(auction_id, &bidder.pubkey()).is_winner_handle;
+        self.withdraw_bid_with(bidder, auction_id, codec::handle_bytes(handle), codec::plaintext_bytes(handle))
+    }
+
+    pub fn withdraw_bid_with(
+        &mut self,
+        bidder: &Keypair,
+        auction_id: u64,
+        is_winner_handle: Vec<u8>,
+        is_winner_plaintext: Vec<u8>,
+    ) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::WithdrawBid {
+                bidder: bidder.pubkey(),
+                auction,
+                bid: bid_pda(&auction, &bidder.pubkey()),
+                vault: vault_pda(&auction),
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::WithdrawBid { is_winner_handle: is_winner_handle.clone(), is_winner_plaintext },
+            vec![],
+        );
+        self.send_attested(ix, &is_winner_handle, bidder)
+    }
+
+    pub fn expire_claim(&mut self, auction_id: u64) -> TransactionResult {
+        let ix = ix(
+            accounts::ExpireClaim { authority: self.authority.pubkey(), auction: auction_pda(auction_id) },
+            instruction::ExpireClaim {},
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &authority)
+    }
+
+    /// Forfeit `bidder`'s bid, presenting the mock's decryption of its `is_winner_handle`
+    pub fn forfeit_bid(&mut self, caller: &Keypair, auction_id: u64, bidder: &Pubkey) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let handle = self.bid(auction_id, bidder).is_winner_handle;
+        let ix = ix(
+            accounts::ForfeitBid {
+                caller: caller.pubkey(),
+                auction,
+                bid: bid_pda(&auction, bidder),
+                bidder: *bidder,
+                vault: vault_pda(&auction),
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            },
+            instruction::ForfeitBid {
+                is_winner_handle: codec::handle_bytes(handle),
+                is_winner_plaintext: codec::plaintext_bytes(handle),
+            },
+            vec![],
+        );
+        self.send_attested(ix, &codec::handle_bytes(handle), caller)
+    }
+
+    pub fn confirm_delivery(&mut self, bidder: &Keypair, auction_id: u64) -> TransactionResult {
+        self.confirm_delivery_as(bidder, auction_id, &bidder.pubkey())
+    }
+
+    pub fn confirm_delivery_as(&mut self, signer: &Keypair, auction_id: u64, bidder: &Pubkey) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::ConfirmDelivery { bidder: signer.pubkey(), auction, bid: bid_pda(&auction, bidder) },
+            instruction::ConfirmDelivery {},
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn release_escrow(&mut self, auction_id: u64, bidder: &Pubkey) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::ReleaseEscrow { authority: self.authority.pubkey(), auction, bid: bid_pda(&auction, bidder) },
+            instruction::ReleaseEscrow {},
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &authority)
+    }
+
+    pub fn create_seller_profile(&mut self, seller: &Keypair, display_name: &str, bio: &str) -> TransactionResult {
+        let ix = ix(
+            accounts::CreateSellerProfile {
+                authority: seller.pubkey(),
+                seller_profile: seller_profile_pda(&seller.pubkey()),
+                system_program: system_program::ID,
+            },
+            instruction::CreateSellerProfile {
+                display_name: display_name.to_string(),
+                bio: bio.to_string(),
+                avatar_url: String::new(),
+            },
+            vec![],
+        );
+        self.send(&[ix], seller)
+    }
+
+    pub fn update_seller_profile(&mut self, seller: &Keypair, display_name: &str, bio: &str, avatar_url: &str) -> TransactionResult {
+        let ix = ix(
+            accounts::UpdateSellerProfile {
+                authority: seller.pubkey(),
+                seller_profile: seller_profile_pda(&seller.pubkey()),
+            },
+            instruction::UpdateSellerProfile {
+                display_name: display_name.to_string(),
+                bio: bio.to_string(),
+                avatar_url: avatar_url.to_string(),
+            },
+            vec![],
+        );
+        self.send(&[ix], seller)
+    }
+
+    pub fn seller_profile(&self, seller: &Pubkey) -> SellerProfile {
+        self.account(&seller_profile_pda(seller))
+    }
+
+    pub fn add_review(&mut self, reviewer: &Keypair, auction_id: u64, score: u8, text: &str) -> TransactionResult {
+        self.add_review_for(reviewer, auction_id, &reviewer.pubkey(), score, text)
+    }
+
+    /// Review presenting `bidder`'s bid, which need not be the reviewer's own
+    pub fn add_review_for(
+        &mut self,
+        reviewer: &Keypair,
+        auction_id: u64,
+        bidder: &Pubkey,
+        score: u8,
+        text: &str,
+    ) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::AddReview {
+                reviewer: reviewer.pubkey(),
+                auction,
+                bid: bid_pda(&auction, bidder),
+                seller_profile: seller_profile_pda(&self.authority.pubkey()),
+                review: review_pda(&auction),
+                system_program: system_program::ID,
+            },
+            instruction::AddReview { score, text: text.to_string() },
+            vec![],
+        );
+        self.send(&[ix], reviewer)
+    }
+
+    pub fn review(&self, auction_id: u64) -> Review {
+        self.account(&review_pda(&auction_pda(auction_id)))
+    }
+
+    pub fn verify_seller(&mut self, signer: &Keypair, seller: &Pubkey, tier: u8) -> TransactionResult {
+        let ix = ix(
+            accounts::VerifySeller {
+                verifier: signer.pubkey(),
+                config: config_pda(),
+                seller_profile: seller_profile_pda(seller),
+            },
+            instruction::VerifySeller { tier },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn revoke_seller(&mut self, signer: &Keypair, seller: &Pubkey) -> TransactionResult {
+        let ix = ix(
+            accounts::RevokeSeller {
+                verifier: signer.pubkey(),
+                config: config_pda(),
+                seller_profile: seller_profile_pda(seller),
+            },
+            instruction::RevokeSeller {},
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn add_comment(&mut self, commenter: &Keypair, auction_id: u64, text: &str) -> TransactionResult {
+        self.add_reply(commenter, auction_id, None, text)
+    }
+
+    /// Posts under the auction's next comment id, passing the commenter's bid whenever
+    /// one exists and their comment record when the auction limits comment frequency
+    pub fn add_reply(&mut self, commenter: &Keypair, auction_id: u64, parent: Option<Pubkey>, text: &str) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let auction_account = self.auction(auction_id);
+        let bid = Some(bid_pda(&auction, &commenter.pubkey())).filter(|b| self.svm.get_account(b).is_some());
+        let comment_record = Some(comment_record_pda(&auction, &commenter.pubkey())).filter(|_| auction_account.comment_interval > 0);
+        let ix = ix(
+            accounts::AddComment {
+                commenter: commenter.pubkey(),
+                auction,
+                comment: comment_pda(&auction, auction_account.comment_count),
+                parent,
+                bid,
+                comment_record,
+                system_program: system_program::ID,
+            },
+            instruction::AddComment { comment_text: text.to_string() },
+            vec![],
+        );
+        self.send(&[ix], commenter)
+    }
+
+    pub fn edit_comment(&mut self, commenter: &Keypair, auction_id: u64, comment_id: u64, text: &str) -> TransactionResult {
+        let ix = ix(
+            accounts::EditComment {
+                commenter: commenter.pubkey(),
+                auction: auction_pda(auction_id),
+                comment: comment_pda(&auction_pda(auction_id), comment_id),
+                system_program: system_program::ID,
+            },
+            instruction::EditComment { comment_text: text.to_string() },
+            vec![],
+        );
+        self.send(&[ix], commenter)
+    }
+
+    /// Passes the comment's parent whenever it has one
+    pub fn delete_comment(&mut self, signer: &Keypair, auction_id: u64, comment_id: u64, commenter: &Pubkey) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let comment = comment_pda(&auction, comment_id);
+        let parent = if self.balance(&comment) > 0 { self.account::<Comment>(&comment).parent } else { None };
+        let ix = ix(
+            accounts::DeleteComment {
+                signer: signer.pubkey(),
+                config: config_pda(),
+                auction,
+                comment,
+                commenter: *commenter,
+                parent,
+            },
+            instruction::DeleteComment {},
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn mark_answer(&mut self, signer: &Keypair, auction_id: u64, question_id: u64, reply_id: u64) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::MarkAnswer {
+                authority: signer.pubkey(),
+                auction,
+                question: comment_pda(&auction, question_id),
+                reply: comment_pda(&auction, reply_id),
+            },
+            instruction::MarkAnswer {},
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn comment(&self, auction_id: u64, comment_id: u64) -> Comment {
+        self.account(&comment_pda(&auction_pda(auction_id), comment_id))
+    }
+
+    pub fn report_auction(&mut self, reporter: &Keypair, auction_id: u64, reason: ReportReason) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::ReportAuction {
+                reporter: reporter.pubkey(),
+                auction,
+                report: report_pda(&auction, &reporter.pubkey()),
+                system_program: system_program::ID,
+            },
+            instruction::ReportAuction { reason },
+            vec![],
+        );
+        self.send(&[ix], reporter)
+    }
+
+    pub fn report_comment(&mut self, reporter: &Keypair, auction_id: u64, comment_id: u64, reason: ReportReason) -> TransactionResult {
+        let comment = comment_pda(&auction_pda(auction_id), comment_id);
+        let ix = ix(
+            accounts::ReportComment {
+                reporter: reporter.pubkey(),
+                comment,
+                report: report_pda(&comment, &reporter.pubkey()),
+                system_program: system_program::ID,
+            },
+            instruction::ReportComment { reason },
+            vec![],
+        );
+        self.send(&[ix], reporter)
+    }
+
+    pub fn report(&self, target: &Pubkey, reporter: &Pubkey) -> Report {
+        self.account(&report_pda(target, reporter))
+    }
+
+    pub fn moderate_auction(&mut self, moderator: &Keypair, auction_id: u64, hidden: bool, frozen: bool) -> TransactionResult {
+        let ix = ix(
+            accounts::ModerateAuction {
+                moderator: moderator.pubkey(),
+                config: config_pda(),
+                auction: auction_pda(auction_id),
+            },
+            instruction::ModerateAuction { hidden, frozen },
+            vec![],
+        );
+        self.send(&[ix], moderator)
+    }
+
+    pub fn refund_bid(&mut self, bidder: &Keypair, auction_id: u64) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::RefundBid {
+                bidder: bidder.pubkey(),
+                auction,
+                bid: bid_pda(&auction, &bidder.pubkey()),
+                vault: vault_pda(&auction),
+                system_program: system_program::ID,
+            },
+            instruction::RefundBid {},
+            vec![],
+        );
+        self.send(&[ix], bidder)
+    }
+
+    pub fn update_auction_metadata(
+        &mut self,
+        signer: &Keypair,
+        auction_id: u64,
+        title: &str,
+        description: &str,
+        tags: Vec<String>,
+    ) -> TransactionResult {
+        let ix = ix(
+            accounts::UpdateAuctionMetadata {
+                authority: signer.pubkey(),
+                auction: auction_pda(auction_id),
+                system_program: system_program::ID,
+            },
+            instruction::UpdateAuctionMetadata {
+                title: title.to_string(),
+                description: description.to_string(),
+                image_url: String::new(),
+                tags,
+            },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn account_len(&self, address: &Pubkey) -> usize {
+        self.svm.get_account(address).map_or(0, |account| account.data.len())
+    }
+
+    pub fn cancel_auction(&mut self, auction_id: u64) -> TransactionResult {
+        let ix = ix(
+            accounts::CancelAuction {
+                authority: self.authority.pubkey(),
+                auction: auction_pda(auction_id),
+                seller_profile: seller_profile_pda(&self.authority.pubkey()),
+            },
+            instruction::CancelAuction {},
+            vec![],
+        );
+        let authority = self.authority.insecure_clone();
+        self.send(&[ix], &authority)
+    }
+
+    pub fn open_dispute(&mut self, signer: &Keypair, auction_id: u64, bidder: &Pubkey, evidence: &str) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::OpenDispute {
+                party: signer.pubkey(),
+                auction,
+                bid: bid_pda(&auction, bidder),
+                dispute: dispute_pda(&bid_pda(&auction, bidder)),
+                system_program: system_program::ID,
+            },
+            instruction::OpenDispute { evidence: evidence.to_string() },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    pub fn add_evidence(&mut self, signer: &Keypair, auction_id: u64, bidder: &Pubkey, evidence: &str) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::AddEvidence {
+                party: signer.pubkey(),
+                auction,
+                bid: bid_pda(&auction, bidder),
+                dispute: dispute_pda(&bid_pda(&auction, bidder)),
+            },
+            instruction::AddEvidence { evidence: evidence.to_string() },
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+
+    /// Resolve, returning the dispute's rent to whoever opened it (the buyer once it is closed)
+    pub fn resolve_dispute(&mut self, arbiter: &Keypair, auction_id: u64, bidder: &Pubkey, buyer_bps: u16) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let dispute = dispute_pda(&bid_pda(&auction, bidder));
+        let opener = match self.svm.get_account(&dispute) {
+            Some(_) => self.dispute(auction_id, bidder).opened_by,
+            None => *bidder,
+        };
+        let ix = ix(
+            accounts::ResolveDispute {
+                arbiter: arbiter.pubkey(),
+                auction,
+                bid: bid_pda(&auction, bidder),
+                dispute,
+                buyer: *bidder,
+                opener,
+                vault: vault_pda(&auction),
+                system_program: system_program::ID,
+            },
+            instruction::ResolveDispute { buyer_bps },
+            vec![],
+        );
+        self.send(&[ix], arbiter)
+    }
+
+    pub fn dispute(&self, auction_id: u64, bidder: &Pubkey) -> Dispute {
+        self.account(&dispute_pda(&bid_pda(&auction_pda(auction_id), bidder)))
+    }
+
+    pub fn settle_auction(&mut self, auction_id: u64) -> TransactionResult {
+        let authority = self.authority.insecure_clone();
+        self.settle_auction_as(&authority, auction_id)
+    }
+
+    pub fn settle_auction_as(&mut self, signer: &Keypair, auction_id: u64) -> TransactionResult {
+        let auction = auction_pda(auction_id);
+        let ix = ix(
+            accounts::SettleAuction {
+                authority: signer.pubkey(),
+                auction,
+                config: config_pda(),
+                seller_profile: seller_profile_pda(&self.authority.pubkey()),
+                vault: vault_pda(&auction),
+                fee_recipient: self.fee_recipient,
+                system_program: system_program::ID,
+            },
+            instruction::SettleAuction {},
+            vec![],
+        );
+        self.send(&[ix], signer)
+    }
+}
+
+pub fn default_config_params(env: &TestEnv) -> ConfigParams {
+    ConfigParams {
+        guardian: env.guardian.pubkey(),
+        fee_bps: FEE_BPS,
+        fee_recipient: env.fee_recipient,
+        min_minimum_bid: 0,
+        max_duration: 0,
+        claim_period: 0,
+        no_show_penalty_bps: 0,
+        delivery_timeout: 0,
+        arbiter: Pubkey::default(),
+        verifier: Pubkey::default(),
+        moderator: Pubkey::default(),
+        allowed_categories: vec![],
+        verified_categories: vec![],
+    }
+}
+
+/// Create an auction with `options` under the default config as changed by `configure`,
+/// bid each amount (deposited in full) from a fresh wallet and close it. Returns the bidders.
+pub fn closed_auction<const N: usize>(
+    env: &mut TestEnv,
+    auction_id: u64,
+    configure: impl FnOnce(&mut ConfigParams),
+    options: AuctionOptions,
+    amounts: [u64; N],
+) -> [Keypair; N] {
+    let admin = env.admin.insecure_clone();
+    let mut params = default_config_params(env);
+    configure(&mut params);
+    env.update_config(&admin, params).unwrap();
+
+    let end_time = env.now() + 60;
+    env.create_auction_with(auction_id, MINIMUM_BID, end_time, options).unwrap();
+    let bidders = amounts.map(|amount| {
+        let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+        env.place_bid(&bidder, auction_id, amount, amount).unwrap();
+        bidder
+    });
+    env.warp_to(end_time);
+    env.close_auction(auction_id).unwrap();
+    bidders
+}
+
+/// [`closed_auction`] whose bidders have all checked their result and withdrawn
+pub fn paid_auction<const N: usize>(
+    env: &mut TestEnv,
+    auction_id: u64,
+    configure: impl FnOnce(&mut ConfigParams),
+    options: AuctionOptions,
+    amounts: [u64; N],
+) -> [Keypair; N] {
+    let bidders = closed_auction(env, auction_id, configure, options, amounts);
+    for bidder in &bidders {
+        env.check_win(bidder, auction_id).unwrap();
+        env.withdraw_bid(bidder, auction_id).unwrap();
+    }
+    bidders
+}
+
+pub fn ix(accounts: impl ToAccountMetas, args: impl InstructionData, remaining: Vec<AccountMeta>) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    Instruction { program_id: blind_auction::ID, accounts: metas, data: args.data() }
+}
+
+pub fn allowance_pda(handle: u128, allowed: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[&handle.to_le_bytes(), allowed.as_ref()], &INCO_LIGHTNING_ID).0
+}
+
+pub fn allowance_accounts(handle: u128, allowed: &Pubkey) -> Vec<AccountMeta> {
+    vec![AccountMeta::new(allowance_pda(handle, allowed), false), AccountMeta::new_readonly(*allowed, false)]
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &blind_auction::ID).0
+}
+
+pub fn auction_pda(auction_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"auction", &auction_id.to_le_bytes()], &blind_auction::ID).0
+}
+
+pub fn vault_pda(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", auction.as_ref()], &blind_auction::ID).0
+}
+
+pub fn allowlist_pda(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"allowlist", auction.as_ref()], &blind_auction::ID).0
+}
+
+pub fn seller_profile_pda(seller: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"seller", seller.as_ref()], &blind_auction::ID).0
+}
+
+pub fn comment_pda(auction: &Pubkey, comment_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"comment", auction.as_ref(), &comment_id.to_le_bytes()], &blind_auction::ID).0
+}
+
+pub fn comment_record_pda(auction: &Pubkey, commenter: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"comment_record", auction.as_ref(), commenter.as_ref()], &blind_auction::ID).0
+}
+
+pub fn report_pda(target: &Pubkey, reporter: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"report", target.as_ref(), reporter.as_ref()], &blind_auction::ID).0
+}
+
+pub fn review_pda(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"review", auction.as_ref()], &blind_auction::ID).0
+}
+
+pub fn dispute_pda(bid: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"dispute", bid.as_ref()], &blind_auction::ID).0
+}
+
+pub fn bid_pda(auction: &Pubkey, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bid", auction.as_ref(), bidder.as_ref()], &blind_auction::ID).0
+}
+
+/// Assert that the transaction failed with `expected` from the blind auction program
+pub fn assert_auction_error(result: TransactionResult, expected: AuctionError) {
+    let err = result.expect_err("transaction should have failed").err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(ERROR_CODE_OFFSET + expected as u32)),
+    );
+}
+
+/// Deploy the blind auction program through the upgradeable loader so that
+/// `initialize_config` can check its upgrade authority
+fn add_upgradeable_program(svm: &mut LiteSVM, program_id: Pubkey, upgrade_authority: &Pubkey) {
+    let elf = std::fs::read(program_path("blind_auction")).expect("blind_auction.so not found, run `anchor build`");
+    let programdata_address = get_program_data_address(&program_id);
+
+    let mut programdata = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*upgrade_authority),
+    })
+    .unwrap();
+    programdata.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+    programdata.extend_from_slice(&elf);
+    let program = bincode::serialize(&UpgradeableLoaderState::Program { programdata_address }).unwrap();
+
+    for (address, data, executable) in [(programdata_address, programdata, false), (program_id, program, true)] {
+        let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+        let owner = solana_sdk_ids::bpf_loader_upgradeable::ID;
+        svm.set_account(address, Account { lamports, data, owner, executable, rent_epoch: 0 }).unwrap();
+    }
+}
+
+fn program_path(name: &str) -> PathBuf {
+    let dir = std::env::var("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    dir.join(format!("{}.so", name))
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use blind_auction::{
+    constants::{MAX_CLAIM_PERIOD, MAX_DELIVERY_TIMEOUT},
+    error::AuctionError,
+};
+use common::*;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Signer,
+};
+
+#[test]
+fn only_upgrade_authority_can_initialize_config() {
+    let mut env = TestEnv::uninitialized();
+    let params = default_config_params(&env);
+
+    let stranger = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.initialize_config_as(&stranger, params.clone()), AuctionError::Unauthorized);
+
+    env.initialize_config(params).unwrap();
+    let config = env.config();
+    assert_eq!(config.admin, env.admin.pubkey());
+    assert_eq!(config.fee_bps, FEE_BPS);
+    assert_eq!(config.pending_admin, Pubkey::default());
+}
+
+#[test]
+fn create_auction_requires_config() {
+    let mut env = TestEnv::uninitialized();
+    let end_time = env.now() + 60;
+    assert!(env.create_auction(1, MINIMUM_BID, end_time).is_err());
+}
+
+#[test]
+fn update_config_is_admin_only_and_capped() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let mut params = default_config_params(&env);
+
+    params.fee_bps = 500;
+    let authority = env.authority.insecure_clone();
+    assert_auction_error(env.update_config(&authority, params.clone()), AuctionError::Unauthorized);
+    env.update_config(&admin, params.clone()).unwrap();
+    assert_eq!(env.config().fee_bps, 500);
+
+    params.fee_bps = 1_001;
+    assert_auction_error(env.update_config(&admin, params), AuctionError::FeeTooHigh);
+}
+
+#[test]
+fn claim_period_and_delivery_timeout_are_bounded() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+
+    let mut params = default_config_params(&env);
+    params.claim_period = MAX_CLAIM_PERIOD + 1;
+    assert_auction_error(env.update_config(&admin, params), AuctionError::InvalidInput);
+
+    let mut params = default_config_params(&env);
+    params.delivery_timeout = MAX_DELIVERY_TIMEOUT + 1;
+    assert_auction_error(env.update_config(&admin, params), AuctionError::InvalidInput);
+
+    let mut params = default_config_params(&env);
+    params.claim_period = MAX_CLAIM_PERIOD;
+    params.delivery_timeout = MAX_DELIVERY_TIMEOUT;
+    env.update_config(&admin, params).unwrap();
+}
+
+#[test]
+fn admin_transfer_takes_two_steps() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let new_admin = env.funded_keypair(LAMPORTS_PER_SOL);
+    let params = default_config_params(&env);
+
+    assert_auction_error(env.accept_admin(&new_admin), AuctionError::NotPendingAdmin);
+    env.transfer_admin(&admin, new_admin.pubkey()).unwrap();
+    assert_eq!(env.config().admin, admin.pubkey());
+
+    let stranger = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.accept_admin(&stranger), AuctionError::NotPendingAdmin);
+
+    env.accept_admin(&new_admin).unwrap();
+    let config = env.config();
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Pubkey::default());
+
+    assert_auction_error(env.update_config(&admin, params.clone()), AuctionError::Unauthorized);
+    env.update_config(&new_admin, params).unwrap();
+}
+
+#[test]
+fn create_auction_enforces_config_limits() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let mut params = default_config_params(&env);
+    params.min_minimum_bid = MINIMUM_BID;
+    params.max_duration = 3600;
+    params.allowed_categories = vec!["NFTs".to_string(), "Art".to_string()];
+    env.update_config(&admin, params).unwrap();
+
+    let now = env.now();
+    assert_auction_error(env.create_auction_in(1, MINIMUM_BID, now + 60, "Gaming"), AuctionError::CategoryNotAllowed);
+    assert_auction_error(env.create_auction(1, MINIMUM_BID - 1, now + 60), AuctionError::MinimumBidTooLow);
+    assert_auction_error(env.create_auction(1, MINIMUM_BID, now + 3601), AuctionError::DurationTooLong);
+
+    env.create_auction_in(1, MINIMUM_BID, now + 3600, "Art").unwrap();
+    assert_eq!(env.auction(1).fee_bps, FEE_BPS);
+}
+
+#[test]
+fn pause_blocks_new_auctions_and_bids_but_not_withdrawals() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(1, MINIMUM_BID, end_time).unwrap();
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let other = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, 1, 20_000_000, 20_000_000).unwrap();
+    env.place_bid(&other, 1, 40_000_000, 40_000_000).unwrap();
+
+    let (admin, guardian) = (env.admin.insecure_clone(), env.guardian.insecure_clone());
+    assert_auction_error(env.set_paused(&bidder, true), AuctionError::Unauthorized);
+    env.set_paused(&guardian, true).unwrap();
+    assert!(env.config().paused);
+
+    let late_bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.place_bid(&late_bidder, 1, MINIMUM_BID, MINIMUM_BID), AuctionError::ProgramPaused);
+    assert_auction_error(env.create_auction(2, MINIMUM_BID, end_time), AuctionError::ProgramPaused);
+
+    // Funds stay recoverable while paused
+    env.warp_to(end_time);
+    env.close_auction(1).unwrap();
+    env.check_win(&bidder, 1).unwrap();
+    env.withdraw_bid(&bidder, 1).unwrap();
+    assert!(env.bid(1, &bidder.pubkey()).withdrawn);
+
+    assert_auction_error(env.set_paused(&guardian, false), AuctionError::Unauthorized);
+    env.set_paused(&admin, false).unwrap();
+    env.create_auction(2, MINIMUM_BID, end_time + 60).unwrap();
+}
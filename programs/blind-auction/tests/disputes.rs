@@ -0,0 +1,118 @@
+mod common;
+
+use blind_auction::{error::AuctionError, state::ConfigParams};
+use common::*;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const AUCTION_ID: u64 = 1;
+const PAYMENT: u64 = 100_000_000;
+
+/// Escrowed auction under `arbiter` whose only bidder won and paid
+fn escrowed_payment(env: &mut TestEnv, arbiter: &Pubkey, options: AuctionOptions) -> Keypair {
+    let options = AuctionOptions { delivery_escrow: true, ..options };
+    let [winner] = paid_auction(env, AUCTION_ID, arbitrated(arbiter), options, [PAYMENT]);
+    winner
+}
+
+/// Config change adding a delivery timeout and global arbiter
+fn arbitrated(arbiter: &Pubkey) -> impl FnOnce(&mut ConfigParams) + '_ {
+    move |params| {
+        params.delivery_timeout = DELIVERY_TIMEOUT;
+        params.arbiter = *arbiter;
+    }
+}
+
+#[test]
+fn arbiter_splits_disputed_escrow() {
+    let mut env = TestEnv::new();
+    let arbiter = env.funded_keypair(LAMPORTS_PER_SOL);
+    let winner = escrowed_payment(&mut env, &arbiter.pubkey(), AuctionOptions::default());
+    let buyer = winner.pubkey();
+
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.open_dispute(&intruder, AUCTION_ID, &buyer, "ipfs://claim"), AuctionError::Unauthorized);
+    env.open_dispute(&winner, AUCTION_ID, &buyer, "ipfs://damaged-item-photos").unwrap();
+    let seller = env.authority.insecure_clone();
+    env.add_evidence(&seller, AUCTION_ID, &buyer, "ipfs://shipping-receipt").unwrap();
+
+    let dispute = env.dispute(AUCTION_ID, &buyer);
+    assert_eq!(dispute.opened_by, buyer);
+    assert_eq!(dispute.amount, PAYMENT);
+    assert_eq!(dispute.evidence, vec!["ipfs://damaged-item-photos", "ipfs://shipping-receipt"]);
+
+    // The escrow is frozen for both parties
+    assert_auction_error(env.confirm_delivery(&winner, AUCTION_ID), AuctionError::EscrowDisputed);
+    let release_at = env.bid(AUCTION_ID, &buyer).escrow_release_at;
+    env.warp_to(release_at);
+    assert_auction_error(env.release_escrow(AUCTION_ID, &buyer), AuctionError::EscrowDisputed);
+
+    assert_auction_error(env.resolve_dispute(&intruder, AUCTION_ID, &buyer, 4_000), AuctionError::Unauthorized);
+    // The buyer opened the dispute, so they get its rent back along with their share
+    let dispute = dispute_pda(&bid_pda(&auction_pda(AUCTION_ID), &buyer));
+    let before = env.balance(&buyer) + env.balance(&dispute);
+    env.resolve_dispute(&arbiter, AUCTION_ID, &buyer, 4_000).unwrap();
+    assert_eq!(env.balance(&buyer), before + 40_000_000);
+    assert_eq!(env.account_len(&dispute), 0);
+    assert_eq!(env.bid(AUCTION_ID, &buyer).dispute_refund, Some(40_000_000));
+    assert!(env.resolve_dispute(&arbiter, AUCTION_ID, &buyer, 4_000).is_err());
+    let result = env.open_dispute(&winner, AUCTION_ID, &buyer, "ipfs://appeal");
+    assert_auction_error(result, AuctionError::DisputeResolved);
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.proceeds, 60_000_000);
+    assert_eq!(auction.escrowed_proceeds, 0);
+
+    env.settle_auction(AUCTION_ID).unwrap();
+    assert_eq!(env.balance(&env.fee_recipient), 60_000_000 * FEE_BPS as u64 / 10_000);
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 0);
+}
+
+#[test]
+fn auction_arbiter_overrides_the_config() {
+    let mut env = TestEnv::new();
+    let global = env.funded_keypair(LAMPORTS_PER_SOL);
+    let arbiter = env.funded_keypair(LAMPORTS_PER_SOL);
+    let options = AuctionOptions { arbiter: Some(arbiter.pubkey()), ..AuctionOptions::default() };
+    let winner = escrowed_payment(&mut env, &global.pubkey(), options);
+    assert_eq!(env.auction(AUCTION_ID).arbiter, arbiter.pubkey());
+
+    let seller = env.authority.insecure_clone();
+    env.open_dispute(&seller, AUCTION_ID, &winner.pubkey(), "ipfs://no-payment-proof").unwrap();
+    let result = env.resolve_dispute(&global, AUCTION_ID, &winner.pubkey(), 0);
+    assert_auction_error(result, AuctionError::Unauthorized);
+
+    // The seller paid for the dispute, so its rent goes back to them
+    let dispute = dispute_pda(&bid_pda(&auction_pda(AUCTION_ID), &winner.pubkey()));
+    let before = env.balance(&seller.pubkey()) + env.balance(&dispute);
+    env.resolve_dispute(&arbiter, AUCTION_ID, &winner.pubkey(), 0).unwrap();
+    assert_eq!(env.balance(&seller.pubkey()), before);
+    assert_eq!(env.auction(AUCTION_ID).proceeds, PAYMENT);
+    assert_eq!(env.bid(AUCTION_ID, &winner.pubkey()).dispute_refund, Some(0));
+}
+
+#[test]
+fn disputes_need_an_arbiter() {
+    let mut env = TestEnv::new();
+    let winner = escrowed_payment(&mut env, &Pubkey::default(), AuctionOptions::default());
+    let result = env.open_dispute(&winner, AUCTION_ID, &winner.pubkey(), "ipfs://claim");
+    assert_auction_error(result, AuctionError::NoArbiter);
+}
+
+#[test]
+fn each_winner_can_dispute_their_own_payment() {
+    let mut env = TestEnv::new();
+    let arbiter = env.funded_keypair(LAMPORTS_PER_SOL);
+    let options = AuctionOptions { units: 2, delivery_escrow: true, ..AuctionOptions::default() };
+    let winners = paid_auction(&mut env, AUCTION_ID, arbitrated(&arbiter.pubkey()), options, [PAYMENT, PAYMENT]);
+
+    // Resolving one dispute leaves the other winner free to open theirs
+    env.open_dispute(&winners[0], AUCTION_ID, &winners[0].pubkey(), "ipfs://first").unwrap();
+    env.resolve_dispute(&arbiter, AUCTION_ID, &winners[0].pubkey(), 10_000).unwrap();
+    env.open_dispute(&winners[1], AUCTION_ID, &winners[1].pubkey(), "ipfs://second").unwrap();
+
+    let dispute = env.dispute(AUCTION_ID, &winners[1].pubkey());
+    assert_eq!(dispute.evidence, vec!["ipfs://second"]);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use blind_auction::{error::AuctionError, state::ConfigParams};
+use common::*;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+
+const AUCTION_ID: u64 = 1;
+const PAYMENT: u64 = 80_000_000;
+
+/// Escrowed auction whose only bidder won and paid
+fn escrowed_payment(env: &mut TestEnv) -> Keypair {
+    let options = AuctionOptions { delivery_escrow: true, ..AuctionOptions::default() };
+    let configure = |params: &mut ConfigParams| params.delivery_timeout = DELIVERY_TIMEOUT;
+    let [winner] = paid_auction(env, AUCTION_ID, configure, options, [PAYMENT]);
+    winner
+}
+
+#[test]
+fn payment_is_held_until_the_buyer_confirms_delivery() {
+    let mut env = TestEnv::new();
+    let winner = escrowed_payment(&mut env);
+
+    let bid = env.bid(AUCTION_ID, &winner.pubkey());
+    assert_eq!(bid.escrow_amount, PAYMENT);
+    assert_eq!(bid.escrow_release_at, env.now() + DELIVERY_TIMEOUT);
+    assert_eq!(env.auction(AUCTION_ID).escrowed_proceeds, PAYMENT);
+    assert_auction_error(env.settle_auction(AUCTION_ID), AuctionError::NoProceeds);
+
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let result = env.confirm_delivery_as(&intruder, AUCTION_ID, &winner.pubkey());
+    assert_auction_error(result, AuctionError::NotBidder);
+
+    env.confirm_delivery(&winner, AUCTION_ID).unwrap();
+    assert_eq!(env.bid(AUCTION_ID, &winner.pubkey()).escrow_amount, 0);
+    assert_eq!(env.auction(AUCTION_ID).escrowed_proceeds, 0);
+    assert_auction_error(env.confirm_delivery(&winner, AUCTION_ID), AuctionError::NothingInEscrow);
+
+    env.settle_auction(AUCTION_ID).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).settled_proceeds, PAYMENT);
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 0);
+}
+
+#[test]
+fn seller_can_release_unconfirmed_payment_after_timeout() {
+    let mut env = TestEnv::new();
+    let winner = escrowed_payment(&mut env);
+
+    assert_auction_error(env.release_escrow(AUCTION_ID, &winner.pubkey()), AuctionError::DeliveryTimeoutNotReached);
+    let release_at = env.bid(AUCTION_ID, &winner.pubkey()).escrow_release_at;
+    env.warp_to(release_at);
+    env.release_escrow(AUCTION_ID, &winner.pubkey()).unwrap();
+    assert_auction_error(env.release_escrow(AUCTION_ID, &winner.pubkey()), AuctionError::NothingInEscrow);
+
+    env.settle_auction(AUCTION_ID).unwrap();
+    let fee = PAYMENT * FEE_BPS as u64 / 10_000;
+    assert_eq!(env.balance(&env.fee_recipient), fee);
+}
+
+#[test]
+fn escrow_requires_a_configured_timeout() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let options = AuctionOptions { delivery_escrow: true, ..AuctionOptions::default() };
+    let result = env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options);
+    assert_auction_error(result, AuctionError::DeliveryEscrowUnavailable);
+}
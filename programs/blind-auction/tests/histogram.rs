@@ -0,0 +1,127 @@
+mod common;
+
+use blind_auction::{
+    bidding,
+    constants::{MAX_HISTOGRAM_BUCKETS, MAX_INSTRUCTION_TRACE, MAX_UNITS},
+    error::AuctionError,
+    state::PricingMode,
+};
+use common::*;
+use mock_inco_lightning::codec;
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+const AUCTION_ID: u64 = 1;
+
+fn histogram_options(buckets: u8) -> AuctionOptions {
+    AuctionOptions { histogram_buckets: buckets, ..AuctionOptions::default() }
+}
+
+#[test]
+fn histogram_counts_bids_per_minimum_bid_multiple() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, histogram_options(3)).unwrap();
+
+    let mut bidders = Vec::new();
+    for amount in [5_000_000, 15_000_000, 25_000_000, 35_000_000, 38_000_000] {
+        let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+        env.place_bid(&bidder, AUCTION_ID, amount, MINIMUM_BID.max(amount)).unwrap();
+        bidders.push(bidder);
+    }
+
+    let auction = env.auction(AUCTION_ID);
+    let thresholds: Vec<u64> = auction.histogram_thresholds().collect();
+    assert_eq!(thresholds, vec![MINIMUM_BID, 2 * MINIMUM_BID, 3 * MINIMUM_BID]);
+    let counts: Vec<u128> = auction.bid_histogram.iter().map(|&h| codec::decrypt(h)).collect();
+    assert_eq!(counts, vec![4, 3, 2]);
+
+    // Only the seller can decrypt the counts, and only after close
+    let authority = env.authority.pubkey();
+    for &handle in &auction.bid_histogram {
+        assert!(env.svm.get_account(&allowance_pda(handle, &authority)).is_none());
+    }
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    for &handle in &auction.bid_histogram {
+        assert!(env.svm.get_account(&allowance_pda(handle, &authority)).is_some());
+        for bidder in &bidders {
+            assert!(env.svm.get_account(&allowance_pda(handle, &bidder.pubkey())).is_none());
+        }
+    }
+}
+
+#[test]
+fn histogram_is_off_by_default() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    assert!(env.auction(AUCTION_ID).bid_histogram.is_empty());
+}
+
+#[test]
+fn histogram_bucket_limits() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let result = env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, histogram_options(MAX_HISTOGRAM_BUCKETS + 1));
+    assert_auction_error(result, AuctionError::InvalidHistogram);
+
+    // The default config allows a zero minimum bid, which leaves nothing to bucket by
+    let result = env.create_auction_with(AUCTION_ID, 0, end_time, histogram_options(1));
+    assert_auction_error(result, AuctionError::InvalidHistogram);
+}
+
+/// Most histogram buckets a uniform-price auction of `MAX_UNITS` can have
+fn max_buckets_at_max_units() -> u8 {
+    (0..=MAX_HISTOGRAM_BUCKETS)
+        .rev()
+        .find(|&b| bidding::place_bid_trace_length(MAX_UNITS as usize + 1, b as usize) <= MAX_INSTRUCTION_TRACE)
+        .unwrap()
+}
+
+#[test]
+fn bids_fit_in_a_transaction_at_the_largest_settings() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let buckets = max_buckets_at_max_units();
+    let options = AuctionOptions {
+        units: MAX_UNITS,
+        pricing: PricingMode::Uniform,
+        histogram_buckets: buckets,
+        ..AuctionOptions::default()
+    };
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options).unwrap();
+
+    // Past the first `units + 1` bids every slot of the top list is compared and swapped
+    let bids = MAX_UNITS as u64 + 3;
+    for i in 0..bids {
+        let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+        let amount = MINIMUM_BID + i * 1_000_000;
+        let mut ix = env.place_bid_ix(&bidder, AUCTION_ID, amount, amount, BidCredentials::default(), vec![]);
+        let budget = [
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1),
+        ];
+        env.allow_bid_handle(&budget, &mut ix, &bidder).unwrap();
+        env.send(&[budget.as_slice(), &[ix]].concat(), &bidder).unwrap();
+    }
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.top_bid_handles.len(), MAX_UNITS as usize + 1);
+    assert_eq!(codec::decrypt(auction.bid_histogram[0]), bids as u128);
+}
+
+#[test]
+fn histogram_and_units_must_fit_in_a_transaction() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let options = AuctionOptions {
+        units: MAX_UNITS,
+        pricing: PricingMode::Uniform,
+        histogram_buckets: max_buckets_at_max_units() + 1,
+        ..AuctionOptions::default()
+    };
+    let result = env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options);
+    assert_auction_error(result, AuctionError::TooManyBidOperations);
+}
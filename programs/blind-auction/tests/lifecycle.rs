@@ -0,0 +1,264 @@
+mod common;
+
+use blind_auction::error::AuctionError;
+use common::*;
+use mock_inco_lightning::codec;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+const AUCTION_ID: u64 = 1;
+
+#[test]
+fn full_flow_refunds_losers_and_keeps_winner_payment() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let bidder1 = env.funded_keypair(LAMPORTS_PER_SOL);
+    let bidder2 = env.funded_keypair(LAMPORTS_PER_SOL);
+    let bidder3 = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder1, AUCTION_ID, 50_000_000, 50_000_000).unwrap();
+    env.place_bid(&bidder2, AUCTION_ID, 100_000_000, 100_000_000).unwrap();
+    env.place_bid(&bidder3, AUCTION_ID, 30_000_000, 30_000_000).unwrap();
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.bidder_count, 3);
+    assert_eq!(codec::decrypt(auction.highest_bid_handle), 100_000_000);
+
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    assert!(env.auction(AUCTION_ID).is_closed);
+
+    for bidder in [&bidder1, &bidder2, &bidder3] {
+        env.check_win(bidder, AUCTION_ID).unwrap();
+    }
+    assert_eq!(codec::decrypt(env.bid(AUCTION_ID, &bidder1.pubkey()).is_winner_handle), 0);
+    assert_eq!(codec::decrypt(env.bid(AUCTION_ID, &bidder2.pubkey()).is_winner_handle), 1);
+    assert_eq!(codec::decrypt(env.bid(AUCTION_ID, &bidder3.pubkey()).is_winner_handle), 0);
+
+    let vault = vault_pda(&auction_pda(AUCTION_ID));
+    let loser_before = env.balance(&bidder1.pubkey());
+    let winner_before = env.balance(&bidder2.pubkey());
+
+    for bidder in [&bidder1, &bidder2, &bidder3] {
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+        assert!(env.bid(AUCTION_ID, &bidder.pubkey()).withdrawn);
+    }
+
+    // Fees are paid by each bidder, so compare against the deposit with a fee margin
+    let fee_margin = 10_000;
+    assert!(env.balance(&bidder1.pubkey()) + fee_margin >= loser_before + 50_000_000);
+    assert!(env.balance(&bidder2.pubkey()) <= winner_before);
+    assert_eq!(env.balance(&vault), 100_000_000);
+}
+
+#[test]
+fn bid_below_minimum_is_rejected() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let result = env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID - 1, MINIMUM_BID - 1);
+    assert_auction_error(result, AuctionError::BidTooLow);
+}
+
+#[test]
+fn bid_after_end_time_is_rejected() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    env.warp_to(end_time);
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let result = env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID);
+    assert_auction_error(result, AuctionError::AuctionEnded);
+}
+
+#[test]
+fn close_requires_authority_and_end_time() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+
+    assert_auction_error(env.close_auction(AUCTION_ID), AuctionError::AuctionNotEnded);
+
+    env.warp_to(end_time);
+    assert_auction_error(env.close_auction_as(&bidder, AUCTION_ID), AuctionError::Unauthorized);
+    env.close_auction(AUCTION_ID).unwrap();
+    assert_auction_error(env.close_auction(AUCTION_ID), AuctionError::AuctionClosed);
+}
+
+#[test]
+fn loser_cannot_claim_to_be_winner() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let loser = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.place_bid(&loser, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    env.check_win(&loser, AUCTION_ID).unwrap();
+
+    let handle = env.bid(AUCTION_ID, &loser.pubkey()).is_winner_handle;
+    let result = env.withdraw_bid_with(&loser, AUCTION_ID, codec::handle_bytes(handle), b"1".to_vec());
+    assert!(result.is_err());
+    assert!(!env.bid(AUCTION_ID, &loser.pubkey()).withdrawn);
+
+    // An attested-true handle that isn't the bid's own result is refused
+    let forged = codec::ebool_handle(true);
+    let result = env.withdraw_bid_with(&loser, AUCTION_ID, codec::handle_bytes(forged), b"1".to_vec());
+    assert_auction_error(result, AuctionError::WinnerHandleMismatch);
+
+    // So is any attested nonzero plaintext, like the bid amount
+    let amount = env.bid(AUCTION_ID, &loser.pubkey()).bid_amount_handle;
+    let result = env.withdraw_bid_with(&loser, AUCTION_ID, codec::handle_bytes(amount), codec::plaintext_bytes(amount));
+    assert_auction_error(result, AuctionError::WinnerHandleMismatch);
+    assert!(!env.bid(AUCTION_ID, &loser.pubkey()).withdrawn);
+}
+
+#[test]
+fn checked_results_are_not_replaced_by_other_checkers() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let stranger = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+
+    // A fresh comparison gets a fresh handle, which the winner's withdraw would no longer match
+    env.check_win(&winner, AUCTION_ID).unwrap();
+    let handle = env.bid(AUCTION_ID, &winner.pubkey()).is_winner_handle;
+    let result = env.determine_winner(&stranger, AUCTION_ID, &winner.pubkey());
+    assert_auction_error(result, AuctionError::AlreadyChecked);
+    assert_eq!(env.bid(AUCTION_ID, &winner.pubkey()).is_winner_handle, handle);
+
+    env.withdraw_bid(&winner, AUCTION_ID).unwrap();
+    assert!(env.bid(AUCTION_ID, &winner.pubkey()).won);
+}
+
+#[test]
+fn withdraw_requires_check_and_only_succeeds_once() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let other = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+    env.place_bid(&other, AUCTION_ID, 40_000_000, 40_000_000).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+
+    let unchecked = codec::ebool_handle(false);
+    let result = env.withdraw_bid_with(&bidder, AUCTION_ID, codec::handle_bytes(unchecked), b"0".to_vec());
+    assert_auction_error(result, AuctionError::NotChecked);
+
+    env.check_win(&bidder, AUCTION_ID).unwrap();
+    env.withdraw_bid(&bidder, AUCTION_ID).unwrap();
+    assert_auction_error(env.withdraw_bid(&bidder, AUCTION_ID), AuctionError::AlreadyWithdrawn);
+}
+
+#[test]
+fn place_bid_requires_allowance_for_the_bidder() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let other = env.funded_keypair(LAMPORTS_PER_SOL);
+    let ix = env.place_bid_ix(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID, BidCredentials::default(), vec![]);
+    let handle = env.simulate_handle(&[ix], &bidder, "Bid handle:").unwrap();
+
+    let result = env.place_bid_with(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID, vec![]);
+    assert_auction_error(result, AuctionError::MissingAllowanceAccounts);
+
+    let wrong_address = allowance_accounts(handle, &other.pubkey());
+    let result = env.place_bid_with(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID, wrong_address);
+    assert_auction_error(result, AuctionError::InvalidAllowedAddress);
+
+    let mut wrong_pda = allowance_accounts(handle, &other.pubkey());
+    wrong_pda[1].pubkey = bidder.pubkey();
+    let result = env.place_bid_with(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID, wrong_pda);
+    assert_auction_error(result, AuctionError::InvalidAllowanceAccount);
+
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).bidder_count, 1);
+}
+
+#[test]
+fn settlement_pays_seller_minus_protocol_fee() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let loser = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.place_bid(&loser, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+
+    // Nothing to settle until the winner confirms by withdrawing
+    assert_auction_error(env.settle_auction(AUCTION_ID), AuctionError::NoProceeds);
+
+    for bidder in [&winner, &loser] {
+        env.check_win(bidder, AUCTION_ID).unwrap();
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+    assert_eq!(env.auction(AUCTION_ID).proceeds, 80_000_000);
+
+    // Fee changes after creation do not apply to this auction
+    let admin = env.admin.insecure_clone();
+    let mut params = default_config_params(&env);
+    params.fee_bps = 0;
+    env.update_config(&admin, params).unwrap();
+
+    assert_auction_error(env.settle_auction_as(&winner, AUCTION_ID), AuctionError::Unauthorized);
+
+    let seller_before = env.balance(&env.authority.pubkey());
+    env.settle_auction(AUCTION_ID).unwrap();
+
+    let fee = 80_000_000 * FEE_BPS as u64 / 10_000;
+    assert_eq!(env.balance(&env.fee_recipient), fee);
+    assert_eq!(env.balance(&env.authority.pubkey()), seller_before + 80_000_000 - fee - 5_000);
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 0);
+    assert!(env.auction(AUCTION_ID).settled);
+
+    assert_auction_error(env.settle_auction(AUCTION_ID), AuctionError::AlreadySettled);
+}
+
+#[test]
+fn seller_learns_winning_bid_and_winner() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let loser = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.place_bid(&loser, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+
+    // Only the seller may decrypt the highest bid
+    let highest = env.auction(AUCTION_ID).highest_bid_handle;
+    assert!(env.svm.get_account(&allowance_pda(highest, &env.authority.pubkey())).is_some());
+    assert!(env.svm.get_account(&allowance_pda(highest, &winner.pubkey())).is_none());
+
+    for bidder in [&loser, &winner] {
+        env.check_win(bidder, AUCTION_ID).unwrap();
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.winning_bids, vec![bid_pda(&auction_pda(AUCTION_ID), &winner.pubkey())]);
+    assert!(env.bid(AUCTION_ID, &winner.pubkey()).won);
+    assert!(!env.bid(AUCTION_ID, &loser.pubkey()).won);
+}
@@ -0,0 +1,195 @@
+//! Property-based lifecycle fuzzing: random sequences of instructions from
+//! several actors, with the auction invariants asserted after every step.
+
+mod common;
+
+use common::*;
+use mock_inco_lightning::codec;
+use proptest::prelude::*;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+
+const AUCTION_ID: u64 = 7;
+const ACTORS: usize = 4;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Bid { actor: usize, steps: u64 },
+    WarpPastEnd,
+    Close { by_authority: bool },
+    CheckWin { actor: usize },
+    DetermineWinner { checker: usize, target: usize },
+    Withdraw { actor: usize },
+    /// Claim to have won regardless of the decrypted result
+    ForgeWin { actor: usize },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    let actor = 0..ACTORS;
+    prop_oneof![
+        4 => (actor.clone(), 0..50u64).prop_map(|(actor, steps)| Action::Bid { actor, steps }),
+        1 => Just(Action::WarpPastEnd),
+        1 => any::<bool>().prop_map(|by_authority| Action::Close { by_authority }),
+        3 => actor.clone().prop_map(|actor| Action::CheckWin { actor }),
+        1 => (actor.clone(), actor.clone()).prop_map(|(checker, target)| Action::DetermineWinner { checker, target }),
+        3 => actor.clone().prop_map(|actor| Action::Withdraw { actor }),
+        1 => actor.prop_map(|actor| Action::ForgeWin { actor }),
+    ]
+}
+
+/// What the test knows independently of the program
+struct Model {
+    amounts: [Option<u64>; ACTORS],
+    withdrawals: [u32; ACTORS],
+}
+
+impl Model {
+    fn highest_bidder(&self) -> Option<usize> {
+        (0..ACTORS).filter(|&i| self.amounts[i].is_some()).max_by_key(|&i| self.amounts[i])
+    }
+}
+
+/// Bid amounts are distinct per actor so there is never a tie for first place
+fn bid_amount(actor: usize, steps: u64) -> u64 {
+    MINIMUM_BID + steps * 1_000_000 + actor as u64
+}
+
+fn run(actions: Vec<Action>) -> Result<(), TestCaseError> {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 3600;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+
+    let actors: Vec<Keypair> = (0..ACTORS).map(|_| env.funded_keypair(10 * LAMPORTS_PER_SOL)).collect();
+    let mut model = Model { amounts: [None; ACTORS], withdrawals: [0; ACTORS] };
+
+    for action in actions {
+        match action {
+            Action::Bid { actor, steps } => {
+                let amount = bid_amount(actor, steps);
+                if env.place_bid(&actors[actor], AUCTION_ID, amount, amount).is_ok() {
+                    prop_assert!(model.amounts[actor].is_none(), "second bid accepted for actor {}", actor);
+                    model.amounts[actor] = Some(amount);
+                }
+            }
+            Action::WarpPastEnd => env.warp_to(end_time),
+            Action::Close { by_authority } => {
+                let closed = if by_authority {
+                    env.close_auction(AUCTION_ID)
+                } else {
+                    env.close_auction_as(&actors[0].insecure_clone(), AUCTION_ID)
+                };
+                prop_assert!(closed.is_err() || by_authority, "non-authority closed the auction");
+            }
+            Action::CheckWin { actor } => {
+                let _ = env.check_win(&actors[actor], AUCTION_ID);
+            }
+            Action::DetermineWinner { checker, target } => {
+                let _ = env.determine_winner(&actors[checker], AUCTION_ID, &actors[target].pubkey());
+            }
+            Action::Withdraw { actor } => {
+                if env.svm.get_account(&bid_pda(&auction_pda(AUCTION_ID), &actors[actor].pubkey())).is_some() {
+                    let handle = env.bid(AUCTION_ID, &actors[actor].pubkey()).is_winner_handle;
+                    let result = env.withdraw_bid_with(
+                        &actors[actor],
+                        AUCTION_ID,
+                        codec::handle_bytes(handle),
+                        codec::plaintext_bytes(handle),
+                    );
+                    if result.is_ok() {
+                        model.withdrawals[actor] += 1;
+                    }
+                }
+            }
+            Action::ForgeWin { actor } => {
+                if env.svm.get_account(&bid_pda(&auction_pda(AUCTION_ID), &actors[actor].pubkey())).is_some() {
+                    let bid = env.bid(AUCTION_ID, &actors[actor].pubkey());
+
+                    // Attested values that aren't the bid's own win result never pay out
+                    let forged = codec::ebool_handle(true);
+                    let result =
+                        env.withdraw_bid_with(&actors[actor], AUCTION_ID, codec::handle_bytes(forged), b"1".to_vec());
+                    prop_assert!(result.is_err(), "withdrew with a foreign true handle");
+                    let amount = bid.bid_amount_handle;
+                    let result = env.withdraw_bid_with(
+                        &actors[actor],
+                        AUCTION_ID,
+                        codec::handle_bytes(amount),
+                        codec::plaintext_bytes(amount),
+                    );
+                    prop_assert!(result.is_err(), "withdrew with the bid amount handle");
+
+                    let handle = bid.is_winner_handle;
+                    let result =
+                        env.withdraw_bid_with(&actors[actor], AUCTION_ID, codec::handle_bytes(handle), b"1".to_vec());
+                    if result.is_ok() {
+                        prop_assert_eq!(Some(actor), model.highest_bidder(), "non-winner withdrew as winner");
+                        model.withdrawals[actor] += 1;
+                    }
+                }
+            }
+        }
+
+        check_invariants(&env, &actors, &model)?;
+    }
+    Ok(())
+}
+
+fn check_invariants(env: &TestEnv, actors: &[Keypair], model: &Model) -> Result<(), TestCaseError> {
+    let auction_key = auction_pda(AUCTION_ID);
+    let auction = env.auction(AUCTION_ID);
+
+    let mut bids = 0;
+    let mut held = 0;
+    let mut winners = Vec::new();
+    let mut all_resolved = true;
+
+    for (i, actor) in actors.iter().enumerate() {
+        prop_assert!(model.withdrawals[i] <= 1, "actor {} withdrew twice", i);
+
+        if env.svm.get_account(&bid_pda(&auction_key, &actor.pubkey())).is_none() {
+            continue;
+        }
+        let bid = env.bid(AUCTION_ID, &actor.pubkey());
+        bids += 1;
+
+        let is_winner = bid.is_winner_handle != 0 && codec::decrypt(bid.is_winner_handle) == 1;
+        if is_winner {
+            winners.push(i);
+        }
+        if bid.is_winner_handle == 0 {
+            all_resolved = false;
+        }
+
+        // Losers are refunded on withdraw, the winner's deposit stays as payment
+        if !bid.withdrawn || is_winner {
+            held += bid.deposit_amount;
+        }
+        prop_assert_eq!(bid.withdrawn, model.withdrawals[i] == 1);
+    }
+
+    prop_assert_eq!(auction.bidder_count, bids);
+    prop_assert_eq!(env.balance(&vault_pda(&auction_key)), held, "vault does not match un-withdrawn deposits");
+
+    prop_assert!(winners.len() <= 1, "more than one winner: {:?}", winners);
+    if auction.is_closed {
+        if let Some(&winner) = winners.first() {
+            prop_assert_eq!(Some(winner), model.highest_bidder());
+        }
+        if all_resolved && bids > 0 {
+            prop_assert_eq!(winners.len(), 1, "no winner once every bid is resolved");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn auction_invariants_hold() {
+    // Load the program fixtures once up front, so a missing `.so` fails here
+    // instead of being saved as a counterexample
+    TestEnv::new();
+    proptest!(ProptestConfig::with_cases(64), |(actions in prop::collection::vec(action(), 1..40))| {
+        run(actions)?;
+    });
+}
@@ -0,0 +1,42 @@
+//! Merkle allowlist helpers, no SVM required.
+
+use anchor_lang::prelude::Pubkey;
+use blind_auction::access::{merkle_proof, merkle_root, verify_merkle_proof};
+use proptest::prelude::*;
+
+#[test]
+fn single_member_root_is_its_leaf() {
+    let member = Pubkey::new_unique();
+    let root = merkle_root(&[member]);
+    assert!(merkle_proof(&[member], 0).is_empty());
+    assert!(verify_merkle_proof(&[], &root, &member));
+    assert!(!verify_merkle_proof(&[], &root, &Pubkey::new_unique()));
+}
+
+#[test]
+fn proof_does_not_transfer_to_another_wallet() {
+    let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let root = merkle_root(&members);
+    let proof = merkle_proof(&members, 2);
+    assert!(verify_merkle_proof(&proof, &root, &members[2]));
+    assert!(!verify_merkle_proof(&proof, &root, &members[3]));
+}
+
+proptest! {
+    #[test]
+    fn every_member_has_a_valid_proof(len in 1usize..40, seed in any::<u64>()) {
+        let members: Vec<Pubkey> =
+            (0..len).map(|i| Pubkey::new_from_array(hash_seed(seed, i))).collect();
+        let root = merkle_root(&members);
+        for (i, member) in members.iter().enumerate() {
+            prop_assert!(verify_merkle_proof(&merkle_proof(&members, i), &root, member));
+        }
+    }
+}
+
+fn hash_seed(seed: u64, index: usize) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    bytes[8..16].copy_from_slice(&(index as u64).to_le_bytes());
+    bytes
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use blind_auction::{
+    error::AuctionError,
+    state::{Auction, PricingMode},
+};
+use common::*;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+const AUCTION_ID: u64 = 1;
+
+#[test]
+fn auction_accounts_are_sized_to_their_metadata() {
+    let mut env = TestEnv::new();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let address = auction_pda(AUCTION_ID);
+    let lists = Auction::lists_space(1, PricingMode::PayAsBid, 0);
+    let expected = Auction::space(lists, "Auction 1", "Test auction", "NFTs", "", &["test".to_string()]);
+    assert_eq!(env.account_len(&address), expected);
+
+    let authority = env.authority.insecure_clone();
+    let tags = vec!["vinyl".to_string(), "first-press".to_string()];
+    let description = "Original 1969 pressing, sleeve in very good condition";
+    let before = env.balance(&address);
+    env.update_auction_metadata(&authority, AUCTION_ID, "Abbey Road LP", description, tags.clone()).unwrap();
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.title, "Abbey Road LP");
+    assert_eq!(auction.description, description);
+    assert_eq!(auction.tags, tags);
+    assert_eq!(auction.category, "NFTs");
+    assert_eq!(env.account_len(&address), Auction::space(lists, "Abbey Road LP", description, "NFTs", "", &tags));
+    assert!(env.balance(&address) > before);
+
+    // Shrinking refunds the difference
+    let before = env.balance(&address);
+    env.update_auction_metadata(&authority, AUCTION_ID, "LP", "", vec![]).unwrap();
+    assert_eq!(env.account_len(&address), Auction::space(lists, "LP", "", "NFTs", "", &[]));
+    assert!(env.balance(&address) < before);
+}
+
+#[test]
+fn auction_lists_are_sized_to_units_and_buckets() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let options =
+        AuctionOptions { units: 3, pricing: PricingMode::Uniform, histogram_buckets: 4, ..AuctionOptions::default() };
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options).unwrap();
+    let lists = Auction::lists_space(3, PricingMode::Uniform, 4);
+    let expected = Auction::space(lists, "Auction 1", "Test auction", "NFTs", "", &["test".to_string()]);
+    assert_eq!(env.account_len(&auction_pda(AUCTION_ID)), expected);
+
+    // Filling every list fits in that space
+    let bidders: Vec<_> = (0..5).map(|_| env.funded_keypair(LAMPORTS_PER_SOL)).collect();
+    for (i, bidder) in bidders.iter().enumerate() {
+        let amount = MINIMUM_BID * (i as u64 + 2);
+        env.place_bid(bidder, AUCTION_ID, amount, amount).unwrap();
+    }
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    env.reveal_clearing_price(AUCTION_ID).unwrap();
+    for bidder in &bidders {
+        env.check_win(bidder, AUCTION_ID).unwrap();
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.top_bid_handles.len(), 4);
+    assert_eq!(auction.winning_bids.len(), 3);
+}
+
+#[test]
+fn metadata_is_fixed_once_bidding_starts() {
+    let mut env = TestEnv::new();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let authority = env.authority.insecure_clone();
+
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let result = env.update_auction_metadata(&intruder, AUCTION_ID, "Mine now", "", vec![]);
+    assert_auction_error(result, AuctionError::Unauthorized);
+    let result = env.update_auction_metadata(&authority, AUCTION_ID, &"x".repeat(101), "", vec![]);
+    assert_auction_error(result, AuctionError::InvalidInput);
+
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    let result = env.update_auction_metadata(&authority, AUCTION_ID, "Bait and switch", "", vec![]);
+    assert_auction_error(result, AuctionError::AuctionHasBids);
+}
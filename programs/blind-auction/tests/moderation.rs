@@ -0,0 +1,206 @@
+mod common;
+
+use blind_auction::{
+    error::AuctionError,
+    state::{PricingMode, ReportReason, ReportTarget},
+};
+use common::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+const AUCTION_ID: u64 = 1;
+
+#[test]
+fn reports_are_counted_once_per_reporter() {
+    let mut env = TestEnv::new();
+    env.create_auction(AUCTION_ID, MINIMUM_BID, env.now() + 60).unwrap();
+    let commenter = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.add_comment(&commenter, AUCTION_ID, "Buy my course").unwrap();
+
+    let first = env.funded_keypair(LAMPORTS_PER_SOL);
+    let second = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.report_auction(&first, AUCTION_ID, ReportReason::Fraud).unwrap();
+    env.report_auction(&second, AUCTION_ID, ReportReason::ProhibitedItem).unwrap();
+    assert!(env.report_auction(&first, AUCTION_ID, ReportReason::Spam).is_err());
+    assert_eq!(env.auction(AUCTION_ID).flag_count, 2);
+
+    let report = env.report(&auction_pda(AUCTION_ID), &first.pubkey());
+    assert_eq!(report.kind, ReportTarget::Auction);
+    assert_eq!(report.reason, ReportReason::Fraud);
+
+    env.report_comment(&first, AUCTION_ID, 0, ReportReason::Spam).unwrap();
+    assert!(env.report_comment(&first, AUCTION_ID, 0, ReportReason::Spam).is_err());
+    assert_eq!(env.comment(AUCTION_ID, 0).flag_count, 1);
+    let comment = comment_pda(&auction_pda(AUCTION_ID), 0);
+    assert_eq!(env.report(&comment, &first.pubkey()).kind, ReportTarget::Comment);
+}
+
+#[test]
+fn frozen_auction_blocks_bids_and_payouts_but_refunds_everyone() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let moderator = env.funded_keypair(LAMPORTS_PER_SOL);
+    let mut params = default_config_params(&env);
+    params.moderator = moderator.pubkey();
+    env.update_config(&admin, params).unwrap();
+
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let loser = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.place_bid(&loser, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+
+    assert_auction_error(env.moderate_auction(&moderator, AUCTION_ID, false, true), AuctionError::NotFlagged);
+    env.report_auction(&loser, AUCTION_ID, ReportReason::Fraud).unwrap();
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.moderate_auction(&intruder, AUCTION_ID, false, true), AuctionError::Unauthorized);
+    assert_auction_error(env.refund_bid(&loser, AUCTION_ID), AuctionError::AuctionNotFrozen);
+    env.moderate_auction(&moderator, AUCTION_ID, true, true).unwrap();
+    let auction = env.auction(AUCTION_ID);
+    assert!(auction.hidden && auction.frozen);
+
+    let late = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.place_bid(&late, AUCTION_ID, MINIMUM_BID, MINIMUM_BID), AuctionError::AuctionFrozen);
+    env.warp_to(end_time);
+    assert_auction_error(env.close_auction(AUCTION_ID), AuctionError::AuctionFrozen);
+
+    for bidder in [&winner, &loser] {
+        let before = env.balance(&bidder.pubkey());
+        env.refund_bid(bidder, AUCTION_ID).unwrap();
+        assert!(env.balance(&bidder.pubkey()) > before);
+        assert_auction_error(env.refund_bid(bidder, AUCTION_ID), AuctionError::AlreadyWithdrawn);
+    }
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 0);
+}
+
+#[test]
+fn moderation_can_be_lifted() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.report_auction(&bidder, AUCTION_ID, ReportReason::Other).unwrap();
+
+    env.moderate_auction(&admin, AUCTION_ID, false, true).unwrap();
+    env.moderate_auction(&admin, AUCTION_ID, false, false).unwrap();
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+}
+
+#[test]
+fn freeze_is_final_once_a_deposit_is_refunded() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let other = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.place_bid(&other, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+    env.report_auction(&other, AUCTION_ID, ReportReason::Fraud).unwrap();
+
+    env.moderate_auction(&admin, AUCTION_ID, false, true).unwrap();
+    env.refund_bid(&winner, AUCTION_ID).unwrap();
+    assert!(env.auction(AUCTION_ID).refunded);
+
+    // The refunded bid is still the highest, so the auction can't resume
+    assert_auction_error(env.moderate_auction(&admin, AUCTION_ID, false, false), AuctionError::AuctionRefunded);
+    env.moderate_auction(&admin, AUCTION_ID, true, true).unwrap();
+    env.refund_bid(&other, AUCTION_ID).unwrap();
+}
+
+#[test]
+fn winners_who_paid_before_the_freeze_get_their_deposit_back() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let mut params = default_config_params(&env);
+    params.delivery_timeout = 7 * 24 * 3_600;
+    env.update_config(&admin, params).unwrap();
+
+    // Two units at a uniform 20M, so each winner gets part of their deposit back at withdraw
+    let end_time = env.now() + 60;
+    let options = AuctionOptions {
+        units: 2,
+        pricing: PricingMode::Uniform,
+        delivery_escrow: true,
+        ..AuctionOptions::default()
+    };
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options).unwrap();
+    let deposits = [50_000_000, 30_000_000, 20_000_000];
+    let bidders: Vec<_> = deposits.iter().map(|_| env.funded_keypair(LAMPORTS_PER_SOL)).collect();
+    for (bidder, &deposit) in bidders.iter().zip(&deposits) {
+        env.place_bid(bidder, AUCTION_ID, deposit, deposit).unwrap();
+    }
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    env.reveal_clearing_price(AUCTION_ID).unwrap();
+
+    // One winner confirms delivery, the other's payment stays in escrow
+    let vault = vault_pda(&auction_pda(AUCTION_ID));
+    let mut returned = [0; 2];
+    for (i, winner) in bidders[..2].iter().enumerate() {
+        env.check_win(winner, AUCTION_ID).unwrap();
+        let before = env.balance(&vault);
+        env.withdraw_bid(winner, AUCTION_ID).unwrap();
+        returned[i] = before - env.balance(&vault);
+    }
+    env.confirm_delivery(&bidders[0], AUCTION_ID).unwrap();
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!((auction.proceeds, auction.escrowed_proceeds), (40_000_000, 20_000_000));
+
+    env.report_auction(&bidders[2], AUCTION_ID, ReportReason::Fraud).unwrap();
+    env.moderate_auction(&admin, AUCTION_ID, false, true).unwrap();
+    for (i, bidder) in bidders.iter().enumerate() {
+        let before = env.balance(&vault);
+        env.refund_bid(bidder, AUCTION_ID).unwrap();
+        let refunded = before - env.balance(&vault);
+        assert_eq!(returned.get(i).copied().unwrap_or_default() + refunded, deposits[i]);
+        assert_auction_error(env.refund_bid(bidder, AUCTION_ID), AuctionError::AlreadyWithdrawn);
+    }
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!((auction.proceeds, auction.escrowed_proceeds), (0, 0));
+    assert_eq!(env.balance(&vault), 0);
+}
+
+#[test]
+fn forfeited_no_show_penalty_is_refunded_from_a_frozen_auction() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let mut params = default_config_params(&env);
+    params.claim_period = 3_600;
+    params.no_show_penalty_bps = 5_000;
+    env.update_config(&admin, params).unwrap();
+
+    let end_time = env.now() + 60;
+    env.create_auction(AUCTION_ID, MINIMUM_BID, end_time).unwrap();
+    let winner = env.funded_keypair(LAMPORTS_PER_SOL);
+    let loser = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&winner, AUCTION_ID, 80_000_000, 80_000_000).unwrap();
+    env.place_bid(&loser, AUCTION_ID, 20_000_000, 20_000_000).unwrap();
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+
+    env.warp_to(env.auction(AUCTION_ID).claim_deadline);
+    env.expire_claim(AUCTION_ID).unwrap();
+    let seller = env.authority.insecure_clone();
+    env.determine_winner(&seller, AUCTION_ID, &winner.pubkey()).unwrap();
+    env.forfeit_bid(&seller, AUCTION_ID, &winner.pubkey()).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).proceeds, 40_000_000);
+
+    // Once the loser is refunded the freeze is final, and the penalty can only go back
+    env.report_auction(&loser, AUCTION_ID, ReportReason::Fraud).unwrap();
+    env.moderate_auction(&admin, AUCTION_ID, false, true).unwrap();
+    assert_auction_error(env.settle_auction(AUCTION_ID), AuctionError::AuctionFrozen);
+    env.refund_bid(&loser, AUCTION_ID).unwrap();
+    assert_auction_error(env.moderate_auction(&admin, AUCTION_ID, false, false), AuctionError::AuctionRefunded);
+
+    let vault = vault_pda(&auction_pda(AUCTION_ID));
+    let before = env.balance(&vault);
+    env.refund_bid(&winner, AUCTION_ID).unwrap();
+    assert_eq!(before - env.balance(&vault), 40_000_000);
+    assert_eq!(env.auction(AUCTION_ID).proceeds, 0);
+    assert_eq!(env.balance(&vault), 0);
+}
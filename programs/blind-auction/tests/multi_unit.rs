@@ -0,0 +1,137 @@
+mod common;
+
+use blind_auction::{constants::MAX_UNITS, error::AuctionError, state::PricingMode};
+use common::*;
+use mock_inco_lightning::codec;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+
+const AUCTION_ID: u64 = 1;
+
+/// Place one bid per amount (deposit = bid), close, and check every bidder
+fn run_auction(env: &mut TestEnv, units: u16, pricing: PricingMode, amounts: &[u64]) -> Vec<Keypair> {
+    let end_time = env.now() + 60;
+    env.create_multi_unit_auction(AUCTION_ID, MINIMUM_BID, end_time, units, pricing).unwrap();
+
+    let bidders: Vec<Keypair> = amounts.iter().map(|_| env.funded_keypair(LAMPORTS_PER_SOL)).collect();
+    for (bidder, &amount) in bidders.iter().zip(amounts) {
+        env.place_bid(bidder, AUCTION_ID, amount, amount).unwrap();
+    }
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+    for bidder in &bidders {
+        env.check_win(bidder, AUCTION_ID).unwrap();
+    }
+    bidders
+}
+
+fn is_winner(env: &TestEnv, bidder: &Keypair) -> bool {
+    codec::decrypt(env.bid(AUCTION_ID, &bidder.pubkey()).is_winner_handle) == 1
+}
+
+#[test]
+fn uniform_price_winners_pay_the_highest_losing_bid() {
+    let mut env = TestEnv::new();
+    let amounts = [50_000_000, 40_000_000, 30_000_000, 20_000_000, 15_000_000];
+    let bidders = run_auction(&mut env, 3, PricingMode::Uniform, &amounts);
+
+    let winners: Vec<bool> = bidders.iter().map(|b| is_winner(&env, b)).collect();
+    assert_eq!(winners, vec![true, true, true, false, false]);
+
+    // Winners wait for the seller to reveal the price, losers do not
+    assert_auction_error(env.withdraw_bid(&bidders[0], AUCTION_ID), AuctionError::ClearingPriceNotRevealed);
+    env.withdraw_bid(&bidders[4], AUCTION_ID).unwrap();
+
+    let handle = env.auction(AUCTION_ID).clearing_price_handle().unwrap();
+    let wrong = env.auction(AUCTION_ID).top_bid_handles[0];
+    let result = env.reveal_clearing_price_with(AUCTION_ID, codec::handle_bytes(wrong), codec::plaintext_bytes(wrong));
+    assert_auction_error(result, AuctionError::ClearingPriceMismatch);
+    let result = env.reveal_clearing_price_with(AUCTION_ID, codec::handle_bytes(handle), b"1".to_vec());
+    assert!(result.is_err());
+
+    env.reveal_clearing_price(AUCTION_ID).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).clearing_price, Some(20_000_000));
+    assert_auction_error(env.reveal_clearing_price(AUCTION_ID), AuctionError::ClearingPriceAlreadyRevealed);
+
+    let vault = vault_pda(&auction_pda(AUCTION_ID));
+    for bidder in &bidders[..4] {
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+    assert_eq!(env.auction(AUCTION_ID).proceeds, 3 * 20_000_000);
+    assert_eq!(env.balance(&vault), 3 * 20_000_000);
+}
+
+#[test]
+fn uniform_price_is_the_minimum_bid_when_every_bid_wins() {
+    let mut env = TestEnv::new();
+    let bidders = run_auction(&mut env, 3, PricingMode::Uniform, &[50_000_000, 30_000_000]);
+
+    assert!(bidders.iter().all(|b| is_winner(&env, b)));
+    assert_eq!(env.auction(AUCTION_ID).clearing_price, Some(MINIMUM_BID));
+    assert_auction_error(env.reveal_clearing_price_with(AUCTION_ID, vec![], vec![]), AuctionError::ClearingPriceAlreadyRevealed);
+
+    for bidder in &bidders {
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+    assert_eq!(env.auction(AUCTION_ID).proceeds, 2 * MINIMUM_BID);
+}
+
+#[test]
+fn pay_as_bid_winners_settle_as_they_withdraw() {
+    let mut env = TestEnv::new();
+    let bidders = run_auction(&mut env, 2, PricingMode::PayAsBid, &[30_000_000, 50_000_000, 40_000_000]);
+
+    let winners: Vec<bool> = bidders.iter().map(|b| is_winner(&env, b)).collect();
+    assert_eq!(winners, vec![false, true, true]);
+    assert_auction_error(env.reveal_clearing_price(AUCTION_ID), AuctionError::NotUniformPricing);
+
+    env.withdraw_bid(&bidders[1], AUCTION_ID).unwrap();
+    env.settle_auction(AUCTION_ID).unwrap();
+    assert_auction_error(env.settle_auction(AUCTION_ID), AuctionError::AlreadySettled);
+
+    // A later winner's payment can still be settled
+    env.withdraw_bid(&bidders[2], AUCTION_ID).unwrap();
+    env.withdraw_bid(&bidders[0], AUCTION_ID).unwrap();
+    env.settle_auction(AUCTION_ID).unwrap();
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.proceeds, 90_000_000);
+    assert_eq!(auction.settled_proceeds, 90_000_000);
+    assert_eq!(auction.winning_bids.len(), 2);
+    let fee = 50_000_000 * FEE_BPS as u64 / 10_000 + 40_000_000 * FEE_BPS as u64 / 10_000;
+    assert_eq!(env.balance(&env.fee_recipient), fee);
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 0);
+}
+
+#[test]
+fn tied_winners_beyond_the_units_are_refunded() {
+    let mut env = TestEnv::new();
+    let bidders = run_auction(&mut env, 2, PricingMode::PayAsBid, &[50_000_000, 30_000_000, 30_000_000]);
+
+    // Both tied bids reach the lowest winning bid, but only two units are for sale
+    assert!(bidders.iter().all(|b| is_winner(&env, b)));
+    let before = env.balance(&bidders[2].pubkey());
+    for bidder in &bidders {
+        env.withdraw_bid(bidder, AUCTION_ID).unwrap();
+    }
+
+    let auction = env.auction(AUCTION_ID);
+    assert_eq!(auction.proceeds, 80_000_000);
+    assert_eq!(auction.winning_bids.len(), 2);
+    assert!(!env.bid(AUCTION_ID, &bidders[2].pubkey()).won);
+    assert!(env.balance(&bidders[2].pubkey()) > before);
+    assert_eq!(env.balance(&vault_pda(&auction_pda(AUCTION_ID))), 80_000_000);
+}
+
+#[test]
+fn units_must_be_within_limits() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    for units in [0, MAX_UNITS + 1] {
+        let result = env.create_multi_unit_auction(AUCTION_ID, MINIMUM_BID, end_time, units, PricingMode::Uniform);
+        assert_auction_error(result, AuctionError::InvalidUnits);
+    }
+    env.create_multi_unit_auction(AUCTION_ID, MINIMUM_BID, end_time, MAX_UNITS, PricingMode::Uniform).unwrap();
+}
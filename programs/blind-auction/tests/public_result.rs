@@ -0,0 +1,62 @@
+mod common;
+
+use blind_auction::error::AuctionError;
+use common::*;
+use mock_inco_lightning::codec;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+const AUCTION_ID: u64 = 1;
+
+fn create_and_close(env: &mut TestEnv, public_result: bool) {
+    let end_time = env.now() + 60;
+    let options = AuctionOptions { public_result, ..AuctionOptions::default() };
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options).unwrap();
+    for amount in [30_000_000, 70_000_000, 50_000_000] {
+        let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+        env.place_bid(&bidder, AUCTION_ID, amount, amount).unwrap();
+    }
+    env.warp_to(end_time);
+    env.close_auction(AUCTION_ID).unwrap();
+}
+
+#[test]
+fn seller_publishes_verified_winning_bid() {
+    let mut env = TestEnv::new();
+    create_and_close(&mut env, true);
+
+    let highest = env.auction(AUCTION_ID).highest_bid_handle;
+    let authority = env.authority.pubkey();
+    assert!(env.svm.get_account(&allowance_pda(highest, &authority)).is_some());
+
+    // The plaintext must be the attested decryption of the highest bid handle
+    let result = env.publish_result_with(AUCTION_ID, codec::handle_bytes(highest), b"1".to_vec());
+    assert!(result.is_err());
+    let other = codec::euint128_handle(30_000_000);
+    let result = env.publish_result_with(AUCTION_ID, codec::handle_bytes(other), codec::plaintext_bytes(other));
+    assert_auction_error(result, AuctionError::ResultHandleMismatch);
+
+    env.publish_result(AUCTION_ID).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).winning_bid, Some(70_000_000));
+    assert_auction_error(env.publish_result(AUCTION_ID), AuctionError::ResultAlreadyPublished);
+}
+
+#[test]
+fn private_auctions_do_not_publish_the_winning_bid() {
+    let mut env = TestEnv::new();
+    create_and_close(&mut env, false);
+
+    assert_auction_error(env.publish_result(AUCTION_ID), AuctionError::ResultNotPublic);
+    assert_eq!(env.auction(AUCTION_ID).winning_bid, None);
+}
+
+#[test]
+fn result_is_published_only_after_close() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let options = AuctionOptions { public_result: true, ..AuctionOptions::default() };
+    env.create_auction_with(AUCTION_ID, MINIMUM_BID, end_time, options).unwrap();
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID).unwrap();
+
+    assert_auction_error(env.publish_result(AUCTION_ID), AuctionError::AuctionNotClosed);
+}
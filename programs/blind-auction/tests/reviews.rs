@@ -0,0 +1,109 @@
+mod common;
+
+use blind_auction::{
+    error::AuctionError,
+    state::{ConfigParams, ReportReason},
+};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+const AUCTION_ID: u64 = 1;
+
+/// Closed auction with a winner who has paid and a loser who has been refunded
+fn reviewable_auction(env: &mut TestEnv) -> [Keypair; 2] {
+    paid_auction(env, AUCTION_ID, |_| {}, AuctionOptions::default(), [80_000_000, 20_000_000])
+}
+
+#[test]
+fn winner_reviews_a_settled_auction_once() {
+    let mut env = TestEnv::new();
+    let [winner, _] = reviewable_auction(&mut env);
+    assert_auction_error(env.add_review(&winner, AUCTION_ID, 4, "As described"), AuctionError::AuctionNotSettled);
+
+    env.settle_auction(AUCTION_ID).unwrap();
+    env.add_review(&winner, AUCTION_ID, 4, "As described").unwrap();
+
+    let review = env.review(AUCTION_ID);
+    assert_eq!(review.reviewer, winner.pubkey());
+    assert_eq!(review.seller, env.authority.pubkey());
+    assert_eq!(review.score, 4);
+    assert_eq!(review.text, "As described");
+    let profile = env.seller_profile(&env.authority.pubkey());
+    assert_eq!((profile.rating_count, profile.rating_total), (1, 4));
+
+    assert!(env.add_review(&winner, AUCTION_ID, 5, "Again").is_err());
+    assert_eq!(env.seller_profile(&env.authority.pubkey()).rating_count, 1);
+}
+
+#[test]
+fn only_the_winner_can_review() {
+    let mut env = TestEnv::new();
+    let [winner, loser] = reviewable_auction(&mut env);
+    env.settle_auction(AUCTION_ID).unwrap();
+
+    assert_auction_error(env.add_review(&loser, AUCTION_ID, 1, "Lost"), AuctionError::NotWinner);
+    let result = env.add_review_for(&loser, AUCTION_ID, &winner.pubkey(), 1, "Borrowed bid");
+    assert_auction_error(result, AuctionError::NotBidder);
+}
+
+#[test]
+fn score_must_be_one_to_five() {
+    let mut env = TestEnv::new();
+    let [winner, _] = reviewable_auction(&mut env);
+    env.settle_auction(AUCTION_ID).unwrap();
+
+    for score in [0, 6] {
+        assert_auction_error(env.add_review(&winner, AUCTION_ID, score, ""), AuctionError::InvalidScore);
+    }
+    env.add_review(&winner, AUCTION_ID, 5, "").unwrap();
+}
+
+#[test]
+fn escrowed_winner_reviews_after_delivery() {
+    let mut env = TestEnv::new();
+    let options = AuctionOptions { delivery_escrow: true, ..AuctionOptions::default() };
+    let configure = |params: &mut ConfigParams| params.delivery_timeout = DELIVERY_TIMEOUT;
+    let [winner] = paid_auction(&mut env, AUCTION_ID, configure, options, [80_000_000]);
+
+    // The payment is still held for delivery
+    assert_auction_error(env.add_review(&winner, AUCTION_ID, 5, "Fast"), AuctionError::AuctionNotSettled);
+    env.confirm_delivery(&winner, AUCTION_ID).unwrap();
+    env.settle_auction(AUCTION_ID).unwrap();
+    env.add_review(&winner, AUCTION_ID, 5, "Fast").unwrap();
+}
+
+#[test]
+fn each_winner_reviews_once_their_own_payment_is_settled() {
+    let mut env = TestEnv::new();
+    let options = AuctionOptions { units: 2, ..AuctionOptions::default() };
+    let [first, second] = closed_auction(&mut env, AUCTION_ID, |_| {}, options, [80_000_000, 60_000_000]);
+    env.check_win(&first, AUCTION_ID).unwrap();
+    env.withdraw_bid(&first, AUCTION_ID).unwrap();
+    env.settle_auction(AUCTION_ID).unwrap();
+
+    // The second payment arrives after the settlement, so only the first winner has been paid for
+    env.check_win(&second, AUCTION_ID).unwrap();
+    env.withdraw_bid(&second, AUCTION_ID).unwrap();
+    assert!(!env.auction(AUCTION_ID).settled);
+    assert_auction_error(env.add_review(&second, AUCTION_ID, 1, "Unpaid"), AuctionError::AuctionNotSettled);
+    env.add_review(&first, AUCTION_ID, 5, "Paid and delivered").unwrap();
+}
+
+#[test]
+fn refunded_winner_cannot_review() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let options = AuctionOptions { units: 2, delivery_escrow: true, ..AuctionOptions::default() };
+    let configure = |params: &mut ConfigParams| params.delivery_timeout = DELIVERY_TIMEOUT;
+    let [delivered, refunded] = paid_auction(&mut env, AUCTION_ID, configure, options, [80_000_000, 60_000_000]);
+    env.confirm_delivery(&delivered, AUCTION_ID).unwrap();
+    env.settle_auction(AUCTION_ID).unwrap();
+
+    // The escrowed payment goes back to its winner when the auction is frozen
+    env.report_auction(&refunded, AUCTION_ID, ReportReason::Fraud).unwrap();
+    env.moderate_auction(&admin, AUCTION_ID, false, true).unwrap();
+    env.refund_bid(&refunded, AUCTION_ID).unwrap();
+    assert!(env.auction(AUCTION_ID).settled);
+    assert_auction_error(env.add_review(&refunded, AUCTION_ID, 1, "Never arrived"), AuctionError::AuctionNotSettled);
+    env.add_review(&delivered, AUCTION_ID, 4, "Arrived").unwrap();
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use blind_auction::{error::AuctionError, state::VerifiedCategory};
+use common::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+#[test]
+fn counters_track_created_settled_and_cancelled_auctions() {
+    let mut env = TestEnv::new();
+    let seller = env.authority.pubkey();
+    let end_time = env.now() + 60;
+    env.create_auction(1, MINIMUM_BID, end_time).unwrap();
+    env.create_auction(2, MINIMUM_BID, end_time).unwrap();
+
+    let bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    env.place_bid(&bidder, 1, 80_000_000, 80_000_000).unwrap();
+    assert_auction_error(env.cancel_auction(1), AuctionError::AuctionHasBids);
+    env.cancel_auction(2).unwrap();
+    assert!(env.auction(2).cancelled);
+    assert_auction_error(env.cancel_auction(2), AuctionError::AuctionClosed);
+    let late_bidder = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.place_bid(&late_bidder, 2, MINIMUM_BID, MINIMUM_BID), AuctionError::AuctionClosed);
+
+    env.warp_to(end_time);
+    env.close_auction(1).unwrap();
+    env.check_win(&bidder, 1).unwrap();
+    env.withdraw_bid(&bidder, 1).unwrap();
+    env.settle_auction(1).unwrap();
+
+    let profile = env.seller_profile(&seller);
+    assert_eq!(profile.authority, seller);
+    assert_eq!(profile.display_name, "Test Seller");
+    assert_eq!(profile.auctions_created, 2);
+    assert_eq!(profile.auctions_settled, 1);
+    assert_eq!(profile.auctions_cancelled, 1);
+}
+
+#[test]
+fn sellers_edit_their_own_profile() {
+    let mut env = TestEnv::new();
+    let seller = env.authority.insecure_clone();
+    env.update_seller_profile(&seller, "Vintage Audio", "Restored turntables", "https://example.com/a.png").unwrap();
+    let profile = env.seller_profile(&seller.pubkey());
+    assert_eq!(profile.display_name, "Vintage Audio");
+    assert_eq!(profile.bio, "Restored turntables");
+    assert_eq!(profile.avatar_url, "https://example.com/a.png");
+
+    let result = env.update_seller_profile(&seller, &"x".repeat(51), "", "");
+    assert_auction_error(result, AuctionError::InvalidInput);
+}
+
+#[test]
+fn first_auction_creates_the_profile() {
+    let mut env = TestEnv::new();
+    env.authority = env.funded_keypair(10 * LAMPORTS_PER_SOL);
+    let seller = env.authority.insecure_clone();
+    let end_time = env.now() + 60;
+    env.create_auction(1, MINIMUM_BID, end_time).unwrap();
+    let profile = env.seller_profile(&seller.pubkey());
+    assert_eq!(profile.authority, seller.pubkey());
+    assert_eq!(profile.auctions_created, 1);
+
+    // Filling in the details later keeps the counters
+    env.create_seller_profile(&seller, "New Seller", "").unwrap();
+    env.create_auction(2, MINIMUM_BID, end_time).unwrap();
+    let profile = env.seller_profile(&seller.pubkey());
+    assert_eq!(profile.display_name, "New Seller");
+    assert_eq!(profile.auctions_created, 2);
+}
+
+#[test]
+fn verified_categories_need_a_high_enough_tier() {
+    let mut env = TestEnv::new();
+    let admin = env.admin.insecure_clone();
+    let verifier = env.funded_keypair(LAMPORTS_PER_SOL);
+    let mut params = default_config_params(&env);
+    params.verifier = verifier.pubkey();
+    params.verified_categories = vec![VerifiedCategory { category: "Collectibles".to_string(), min_tier: 2 }];
+    env.update_config(&admin, params).unwrap();
+
+    let seller = env.authority.pubkey();
+    let end_time = env.now() + 60;
+    assert_auction_error(env.create_auction_in(1, MINIMUM_BID, end_time, "Collectibles"), AuctionError::SellerNotVerified);
+    env.create_auction_in(1, MINIMUM_BID, end_time, "NFTs").unwrap();
+
+    let intruder = env.funded_keypair(LAMPORTS_PER_SOL);
+    assert_auction_error(env.verify_seller(&intruder, &seller, 2), AuctionError::Unauthorized);
+    assert_auction_error(env.verify_seller(&verifier, &seller, 0), AuctionError::InvalidInput);
+
+    env.verify_seller(&verifier, &seller, 1).unwrap();
+    assert_auction_error(env.create_auction_in(2, MINIMUM_BID, end_time, "Collectibles"), AuctionError::SellerNotVerified);
+    env.verify_seller(&admin, &seller, 2).unwrap();
+    assert_eq!(env.seller_profile(&seller).verification_tier, 2);
+    env.create_auction_in(2, MINIMUM_BID, end_time, "Collectibles").unwrap();
+
+    assert_auction_error(env.revoke_seller(&intruder, &seller), AuctionError::Unauthorized);
+    env.revoke_seller(&verifier, &seller).unwrap();
+    assert_eq!(env.seller_profile(&seller).verification_tier, 0);
+    assert_auction_error(env.create_auction_in(3, MINIMUM_BID, end_time, "Collectibles"), AuctionError::SellerNotVerified);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use blind_auction::{
+    error::AuctionError,
+    state::{TokenGate, TokenGateKind},
+};
+use common::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer};
+
+const AUCTION_ID: u64 = 4;
+
+fn gate_credentials(token_account: Pubkey, metadata: Option<Pubkey>) -> BidCredentials {
+    BidCredentials { gate_token_account: Some(token_account), gate_metadata: metadata, ..Default::default() }
+}
+
+#[test]
+fn mint_gate_requires_enough_tokens_owned_by_the_bidder() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let mint = Pubkey::new_unique();
+    let gate = TokenGate { kind: TokenGateKind::Mint, key: mint, min_amount: 100 };
+    env.create_gated_auction(AUCTION_ID, MINIMUM_BID, end_time, gate).unwrap();
+
+    let holder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let small_holder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let holder_account = env.set_token_account(&holder.pubkey(), &mint, 100);
+    let small_account = env.set_token_account(&small_holder.pubkey(), &mint, 99);
+    let other_mint_account = env.set_token_account(&holder.pubkey(), &Pubkey::new_unique(), 1_000);
+
+    assert_auction_error(env.place_bid(&holder, AUCTION_ID, MINIMUM_BID, MINIMUM_BID), AuctionError::MissingAccount);
+    assert_auction_error(
+        env.place_bid_with_credentials(&holder, AUCTION_ID, MINIMUM_BID, gate_credentials(other_mint_account, None)),
+        AuctionError::InvalidGateAccount,
+    );
+    // Someone else's token account does not count
+    assert_auction_error(
+        env.place_bid_with_credentials(&small_holder, AUCTION_ID, MINIMUM_BID, gate_credentials(holder_account, None)),
+        AuctionError::InvalidGateAccount,
+    );
+    assert_auction_error(
+        env.place_bid_with_credentials(&small_holder, AUCTION_ID, MINIMUM_BID, gate_credentials(small_account, None)),
+        AuctionError::GateBalanceTooLow,
+    );
+
+    env.place_bid_with_credentials(&holder, AUCTION_ID, MINIMUM_BID, gate_credentials(holder_account, None)).unwrap();
+    assert_eq!(env.auction(AUCTION_ID).bidder_count, 1);
+}
+
+#[test]
+fn collection_gate_requires_a_verified_collection_nft() {
+    let mut env = TestEnv::new();
+    let end_time = env.now() + 60;
+    let collection = Pubkey::new_unique();
+    let gate = TokenGate { kind: TokenGateKind::Collection, key: collection, min_amount: 1 };
+    env.create_gated_auction(AUCTION_ID, MINIMUM_BID, end_time, gate).unwrap();
+
+    let holder = env.funded_keypair(LAMPORTS_PER_SOL);
+    let nft = Pubkey::new_unique();
+    let nft_account = env.set_token_account(&holder.pubkey(), &nft, 1);
+    let nft_metadata = env.set_nft_metadata(&nft, &collection, true);
+
+    let unverified = Pubkey::new_unique();
+    let unverified_account = env.set_token_account(&holder.pubkey(), &unverified, 1);
+    let unverified_metadata = env.set_nft_metadata(&unverified, &collection, false);
+
+    assert_auction_error(
+        env.place_bid_with_credentials(&holder, AUCTION_ID, MINIMUM_BID, gate_credentials(nft_account, None)),
+        AuctionError::MissingAccount,
+    );
+    assert_auction_error(
+        env.place_bid_with_credentials(
+            &holder,
+            AUCTION_ID,
+            MINIMUM_BID,
+            gate_credentials(unverified_account, Some(unverified_metadata)),
+        ),
+        AuctionError::InvalidGateAccount,
+    );
+    // Metadata of a different NFT than the one held
+    assert_auction_error(
+        env.place_bid_with_credentials(
+            &holder,
+            AUCTION_ID,
+            MINIMUM_BID,
+            gate_credentials(unverified_account, Some(nft_metadata)),
+        ),
+        AuctionError::InvalidGateAccount,
+    );
+
+    env.place_bid_with_credentials(&holder, AUCTION_ID, MINIMUM_BID, gate_credentials(nft_account, Some(nft_metadata)))
+        .unwrap();
+    assert_eq!(env.auction(AUCTION_ID).bidder_count, 1);
+}
@@ -39,6 +39,8 @@ describe("blind-auction", () => {
   const BIDDER2_BID = 100_000_000; // 0.1 SOL (highest)
   const BIDDER3_BID = 30_000_000; // 0.03 SOL
 
+  let configPda: PublicKey;
+  let sellerProfilePda: PublicKey;
   let auctionPda: PublicKey;
   let vaultPda: PublicKey;
   let bid1Pda: PublicKey;
@@ -59,6 +61,8 @@ describe("blind-auction", () => {
     const idBuffer = Buffer.alloc(8);
     idBuffer.writeBigUInt64LE(BigInt(auctionId));
 
+    [configPda] = PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId);
+    [sellerProfilePda] = PublicKey.findProgramAddressSync([Buffer.from("seller"), wallet.publicKey.toBuffer()], program.programId);
     [auctionPda] = PublicKey.findProgramAddressSync([Buffer.from("auction"), idBuffer], program.programId);
     [vaultPda] = PublicKey.findProgramAddressSync([Buffer.from("vault"), auctionPda.toBuffer()], program.programId);
     [bid1Pda] = PublicKey.findProgramAddressSync([Buffer.from("bid"), auctionPda.toBuffer(), bidder1.publicKey.toBuffer()], program.programId);
@@ -73,6 +77,17 @@ describe("blind-auction", () => {
     return PublicKey.findProgramAddressSync([buf, allowedAddress.toBuffer()], INCO_LIGHTNING_PROGRAM_ID);
   }
 
+  // Handles the seller is granted at close, in the order close_auction expects their allowance accounts
+  function sellerHandles(auction: any): bigint[] {
+    const handles: bigint[] = [];
+    if (auction.pricing.uniform && auction.topBidHandles.length > auction.units) {
+      handles.push(BigInt(auction.topBidHandles[auction.units].toString()));
+    }
+    handles.push(BigInt(auction.highestBidHandle.toString()));
+    for (const handle of auction.bidHistogram) handles.push(BigInt(handle.toString()));
+    return handles;
+  }
+
   async function decryptHandle(handle: string, keypair: Keypair): Promise<{ plaintext: string; ed25519Instructions: any[] } | null> {
     await new Promise(r => setTimeout(r, 2000));
     try {
@@ -146,7 +161,81 @@ describe("blind-auction", () => {
     }
   }
 
+  async function placeBid(bidder: Keypair, bidPda: PublicKey, amount: number) {
+    const encryptedBid = await encryptValue(BigInt(amount));
+    const builder = () =>
+      program.methods
+        .placeBid(hexToBuffer(encryptedBid), new anchor.BN(amount), [])
+        .accounts({
+          bidder: bidder.publicKey,
+          auction: auctionPda,
+          config: configPda,
+          allowlist: null,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          bid: bidPda,
+          vault: vaultPda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .signers([bidder]);
+
+    // The bid handle is only known after the Inco CPI, so simulate first to derive its allowance PDA
+    const bidHandle = await getHandleFromSimulation(await builder().transaction(), "Bid handle:", bidder);
+    if (!bidHandle) throw new Error("Bid handle not found in simulation logs");
+    const [allowancePda] = deriveAllowancePda(bidHandle, bidder.publicKey);
+
+    return builder()
+      .remainingAccounts([
+        { pubkey: allowancePda, isSigner: false, isWritable: true },
+        { pubkey: bidder.publicKey, isSigner: false, isWritable: false },
+      ])
+      .rpc();
+  }
+
+  it("0. Initialize protocol config (if needed)", async () => {
+    const existing = await connection.getAccountInfo(configPda);
+    if (existing) {
+      console.log("Config already initialized");
+      return;
+    }
+
+    // Only the program's upgrade authority can create the config
+    const [programDataPda] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    const tx = await program.methods
+      .initializeConfig({
+        guardian: wallet.publicKey,
+        feeBps: 250,
+        feeRecipient: wallet.publicKey,
+        minMinimumBid: new anchor.BN(0),
+        maxDuration: new anchor.BN(0),
+        claimPeriod: new anchor.BN(0),
+        noShowPenaltyBps: 0,
+        deliveryTimeout: new anchor.BN(0),
+        arbiter: PublicKey.default,
+        verifier: PublicKey.default,
+        moderator: PublicKey.default,
+        allowedCategories: [],
+        verifiedCategories: [],
+      })
+      .accounts({
+        admin: wallet.publicKey,
+        config: configPda,
+        program: program.programId,
+        programData: programDataPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log("Config initialized:", tx);
+    console.log("   Protocol fee: 2.5%");
+  });
+
   it("1. Create auction", async () => {
+    // The seller's profile is created with their first auction
     const tx = await program.methods
       .createAuction(
         new anchor.BN(auctionId),
@@ -156,10 +245,24 @@ describe("blind-auction", () => {
         "This is a test auction for blind bidding",
         "NFTs",
         "https://example.com/image.jpg",
-        ["test", "auction", "blind"]
+        ["test", "auction", "blind"],
+        {
+          access: { open: {} },
+          tokenGate: null,
+          units: 1,
+          pricing: { payAsBid: {} },
+          histogramBuckets: 0,
+          publicResult: false,
+          deliveryEscrow: false,
+          arbiter: null,
+          commentPolicy: { open: {} },
+          commentInterval: new anchor.BN(0),
+        }
       )
       .accounts({
         authority: wallet.publicKey,
+        config: configPda,
+        sellerProfile: sellerProfilePda,
         auction: auctionPda,
         vault: vaultPda,
         systemProgram: SystemProgram.programId,
@@ -188,60 +291,21 @@ describe("blind-auction", () => {
 
   it("3. Bidder 1 places bid", async () => {
     console.log("   Bidder 1 bid:", BIDDER1_BID / 1e9, "SOL (encrypted, nobody sees this!)");
-    const encryptedBid = await encryptValue(BigInt(BIDDER1_BID));
-
-    const tx = await program.methods
-      .placeBid(hexToBuffer(encryptedBid), new anchor.BN(BIDDER1_BID))
-      .accounts({
-        bidder: bidder1.publicKey,
-        auction: auctionPda,
-        bid: bid1Pda,
-        vault: vaultPda,
-        systemProgram: SystemProgram.programId,
-        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
-      } as any)
-      .signers([bidder1])
-      .rpc();
+    const tx = await placeBid(bidder1, bid1Pda, BIDDER1_BID);
 
     console.log("Bidder 1 bid placed:", tx);
   });
 
   it("4. Bidder 2 places bid (highest)", async () => {
     console.log("   Bidder 2 bid:", BIDDER2_BID / 1e9, "SOL (encrypted, nobody sees this!)");
-    const encryptedBid = await encryptValue(BigInt(BIDDER2_BID));
-
-    const tx = await program.methods
-      .placeBid(hexToBuffer(encryptedBid), new anchor.BN(BIDDER2_BID))
-      .accounts({
-        bidder: bidder2.publicKey,
-        auction: auctionPda,
-        bid: bid2Pda,
-        vault: vaultPda,
-        systemProgram: SystemProgram.programId,
-        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
-      } as any)
-      .signers([bidder2])
-      .rpc();
+    const tx = await placeBid(bidder2, bid2Pda, BIDDER2_BID);
 
     console.log("Bidder 2 bid placed:", tx);
   });
 
   it("5. Bidder 3 places bid", async () => {
     console.log("   Bidder 3 bid:", BIDDER3_BID / 1e9, "SOL (encrypted, nobody sees this!)");
-    const encryptedBid = await encryptValue(BigInt(BIDDER3_BID));
-
-    const tx = await program.methods
-      .placeBid(hexToBuffer(encryptedBid), new anchor.BN(BIDDER3_BID))
-      .accounts({
-        bidder: bidder3.publicKey,
-        auction: auctionPda,
-        bid: bid3Pda,
-        vault: vaultPda,
-        systemProgram: SystemProgram.programId,
-        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
-      } as any)
-      .signers([bidder3])
-      .rpc();
+    const tx = await placeBid(bidder3, bid3Pda, BIDDER3_BID);
 
     console.log("Bidder 3 bid placed:", tx);
   });
@@ -255,12 +319,22 @@ describe("blind-auction", () => {
       await new Promise(r => setTimeout(r, waitTime));
     }
 
+    // The seller is granted the highest bid (and any clearing price and histogram) at close
+    const auction = await program.account.auction.fetch(auctionPda);
+    const allowances = sellerHandles(auction).flatMap((handle) => [
+      { pubkey: deriveAllowancePda(handle, wallet.publicKey)[0], isSigner: false, isWritable: true },
+      { pubkey: wallet.publicKey, isSigner: false, isWritable: false },
+    ]);
+
     const tx = await program.methods
       .closeAuction()
       .accounts({
         authority: wallet.publicKey,
         auction: auctionPda,
+        systemProgram: SystemProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
       } as any)
+      .remainingAccounts(allowances)
       .rpc();
 
     console.log("Auction closed:", tx);
@@ -576,4 +650,30 @@ describe("blind-auction", () => {
       console.log("   💰 Bidder 1 got their deposit back:", Number(bid.depositAmount) / 1e9, "SOL");
     }
   });
+
+  it("11. Seller settles proceeds minus the protocol fee", async () => {
+    const auction = await program.account.auction.fetch(auctionPda);
+    if (auction.proceeds.isZero()) {
+      console.log("   No winner payment confirmed yet, skipping settlement");
+      return;
+    }
+
+    const config = await program.account.config.fetch(configPda);
+    const tx = await program.methods
+      .settleAuction()
+      .accounts({
+        authority: wallet.publicKey,
+        auction: auctionPda,
+        config: configPda,
+        sellerProfile: sellerProfilePda,
+        vault: vaultPda,
+        feeRecipient: config.feeRecipient,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    console.log("Auction settled:", tx);
+    console.log("   Proceeds:", Number(auction.proceeds) / 1e9, "SOL");
+    console.log("   Protocol fee:", auction.feeBps, "bps");
+  });
 });
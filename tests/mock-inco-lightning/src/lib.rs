@@ -0,0 +1,251 @@
+#![allow(unexpected_cfgs)]
+
+//! Deterministic stand-in for the Inco Lightning program.
+//!
+//! Exposes the same instructions the `inco-lightning` CPI client calls, but
+//! "encrypts" by tagging the plaintext into the handle itself, so tests can
+//! run the full auction flow on a local SVM without the Inco network.
+//!
+//! - ciphertext passed to `new_euint128` is the plaintext as 16 LE bytes
+//! - an euint128 handle is `EUINT_TAG | nonce | value`, an ebool handle is
+//!   `EBOOL_TAG | nonce | bit`, with the value in the low 64 bits
+//! - each new handle gets a fresh nonce, as on the real service: it hashes the
+//!   operation, its inputs, the signer and the slot, which the harness advances
+//!   after every transaction
+//! - `attest_decryption` stands in for the covalidator's Ed25519 attestation and
+//!   only succeeds for an address holding an allowance on the handle
+//! - `is_validsignature` accepts a plaintext only if it matches its handle and an
+//!   earlier instruction of the transaction attested it to the signer
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hashv, sysvar::instructions};
+use anchor_lang::Discriminator;
+
+declare_id!("5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj");
+
+pub const EUINT_TAG: u128 = 1 << 127;
+pub const EBOOL_TAG: u128 = 1 << 126;
+const VALUE_MASK: u128 = u64::MAX as u128;
+const NONCE_MASK: u128 = !(EUINT_TAG | EBOOL_TAG | VALUE_MASK);
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Euint128(pub u128);
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ebool(pub u128);
+
+/// Helpers for tests to produce inputs and read results the way the mock encodes them
+pub mod codec {
+    use super::*;
+
+    /// Ciphertext accepted by `new_euint128`
+    pub fn encrypt(value: u128) -> Vec<u8> {
+        value.to_le_bytes().to_vec()
+    }
+
+    /// Handle of `value` without a nonce. The mock never issues one, so it forges an input.
+    pub fn euint128_handle(value: u128) -> u128 {
+        EUINT_TAG | value
+    }
+
+    /// Handle of `value` without a nonce. The mock never issues one, so it forges an input.
+    pub fn ebool_handle(value: bool) -> u128 {
+        EBOOL_TAG | value as u128
+    }
+
+    /// Plaintext behind a handle
+    pub fn decrypt(handle: u128) -> u128 {
+        handle & VALUE_MASK
+    }
+
+    /// Handle bytes as passed to `is_validsignature`
+    pub fn handle_bytes(handle: u128) -> Vec<u8> {
+        handle.to_le_bytes().to_vec()
+    }
+
+    /// Decimal plaintext bytes as produced by the attested-decrypt SDK
+    pub fn plaintext_bytes(handle: u128) -> Vec<u8> {
+        decrypt(handle).to_string().into_bytes()
+    }
+}
+
+#[program]
+pub mod mock_inco_lightning {
+    use super::*;
+
+    pub fn new_euint128(ctx: Context<Operation>, ciphertext: Vec<u8>, _input_type: u8) -> Result<Euint128> {
+        let bytes: [u8; 16] = ciphertext
+            .as_slice()
+            .try_into()
+            .map_err(|_| error!(MockIncoError::InvalidCiphertext))?;
+        let value = u128::from_le_bytes(bytes);
+        Ok(Euint128(fresh_handle(EUINT_TAG, value, b"new_euint128", &ctx.accounts.signer, &[value])?))
+    }
+
+    pub fn as_euint128(ctx: Context<Operation>, value: u128) -> Result<Euint128> {
+        Ok(Euint128(fresh_handle(EUINT_TAG, value, b"as_euint128", &ctx.accounts.signer, &[value])?))
+    }
+
+    pub fn e_add(ctx: Context<Operation>, lhs: Euint128, rhs: Euint128, _scalar_byte: u8) -> Result<Euint128> {
+        let sum = codec::decrypt(lhs.0).wrapping_add(codec::decrypt(rhs.0));
+        Ok(Euint128(fresh_handle(EUINT_TAG, sum, b"e_add", &ctx.accounts.signer, &[lhs.0, rhs.0])?))
+    }
+
+    pub fn e_ge(ctx: Context<Operation>, lhs: Euint128, rhs: Euint128, _scalar_byte: u8) -> Result<Ebool> {
+        let result = codec::decrypt(lhs.0) >= codec::decrypt(rhs.0);
+        Ok(Ebool(fresh_handle(EBOOL_TAG, result as u128, b"e_ge", &ctx.accounts.signer, &[lhs.0, rhs.0])?))
+    }
+
+    pub fn e_select(
+        _ctx: Context<Operation>,
+        condition: Ebool,
+        if_true: Euint128,
+        if_false: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        Ok(if codec::decrypt(condition.0) != 0 { if_true } else { if_false })
+    }
+
+    pub fn allow(ctx: Context<Allow>, handle: u128, value: bool, allowed_address: Pubkey) -> Result<()> {
+        let handle_bytes = handle.to_le_bytes();
+        let (expected, bump) =
+            Pubkey::find_program_address(&[&handle_bytes, allowed_address.as_ref()], &ID);
+        require_keys_eq!(ctx.accounts.allowance_account.key(), expected, MockIncoError::InvalidAllowance);
+        require_keys_eq!(ctx.accounts.allowed_address.key(), allowed_address, MockIncoError::InvalidAllowance);
+
+        let allowance = &ctx.accounts.allowance_account;
+        if allowance.lamports() == 0 {
+            let space = 1;
+            let seeds: &[&[u8]] = &[&handle_bytes, allowed_address.as_ref(), &[bump]];
+            anchor_lang::system_program::create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::CreateAccount {
+                        from: ctx.accounts.signer.to_account_info(),
+                        to: allowance.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                &ID,
+            )?;
+        }
+        allowance.try_borrow_mut_data()?[0] = value as u8;
+        Ok(())
+    }
+
+    /// Attest a decryption of `handle` to `allowed_address`, which must hold an allowance on it
+    pub fn attest_decryption(ctx: Context<AttestDecryption>, handle: u128) -> Result<()> {
+        let (expected, _) =
+            Pubkey::find_program_address(&[&handle.to_le_bytes(), ctx.accounts.allowed_address.key.as_ref()], &ID);
+        let allowance = &ctx.accounts.allowance_account;
+        require_keys_eq!(allowance.key(), expected, MockIncoError::InvalidAllowance);
+        let allowed = allowance.owner == &ID && allowance.try_borrow_data()?.first() == Some(&1);
+        require!(allowed, MockIncoError::NotAllowed);
+        Ok(())
+    }
+
+    pub fn is_validsignature(
+        ctx: Context<VerifySignature>,
+        count: u8,
+        handles: Option<Vec<Vec<u8>>>,
+        plaintexts: Option<Vec<Vec<u8>>>,
+    ) -> Result<()> {
+        let handles = handles.unwrap_or_default();
+        let plaintexts = plaintexts.unwrap_or_default();
+        require!(
+            handles.len() == count as usize && plaintexts.len() == count as usize,
+            MockIncoError::InvalidSignature
+        );
+
+        let attested = attested_handles(&ctx.accounts.instructions, ctx.accounts.signer.key)?;
+        for (handle, plaintext) in handles.iter().zip(plaintexts.iter()) {
+            let handle: [u8; 16] = handle
+                .as_slice()
+                .try_into()
+                .map_err(|_| error!(MockIncoError::InvalidSignature))?;
+            let handle = u128::from_le_bytes(handle);
+            require!(attested.contains(&handle), MockIncoError::NotAllowed);
+            require!(*plaintext == codec::plaintext_bytes(handle), MockIncoError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
+/// Handle for the result of `op`, with a nonce no earlier transaction could have produced
+fn fresh_handle(tag: u128, value: u128, op: &[u8], signer: &Signer, inputs: &[u128]) -> Result<u128> {
+    let slot = Clock::get()?.slot.to_le_bytes();
+    let inputs: Vec<u8> = inputs.iter().flat_map(|input| input.to_le_bytes()).collect();
+    let hash = hashv(&[op, signer.key.as_ref(), &slot, &inputs]).to_bytes();
+    let nonce = u128::from_le_bytes(hash[..16].try_into().unwrap()) & NONCE_MASK;
+    Ok(tag | nonce | (value & VALUE_MASK))
+}
+
+/// Handles that `attest_decryption` instructions earlier in the transaction attested to `signer`
+fn attested_handles(sysvar: &AccountInfo, signer: &Pubkey) -> Result<Vec<u128>> {
+    let discriminator = instruction::AttestDecryption::DISCRIMINATOR;
+    let current = instructions::load_current_index_checked(sysvar)?;
+    let mut handles = Vec::new();
+    for index in 0..current {
+        let ix = instructions::load_instruction_at_checked(index as usize, sysvar)?;
+        let attests = ix.program_id == ID
+            && ix.data.starts_with(discriminator)
+            && ix.accounts.get(1).is_some_and(|allowed| allowed.pubkey == *signer);
+        if attests {
+            let args = instruction::AttestDecryption::try_from_slice(&ix.data[discriminator.len()..])?;
+            handles.push(args.handle);
+        }
+    }
+    Ok(handles)
+}
+
+#[derive(Accounts)]
+pub struct Operation<'info> {
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Allow<'info> {
+    /// CHECK: allowance PDA, derivation checked in the handler
+    #[account(mut)]
+    pub allowance_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: address being granted access
+    pub allowed_address: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestDecryption<'info> {
+    /// CHECK: allowance PDA, derivation checked in the handler
+    pub allowance_account: AccountInfo<'info>,
+
+    /// CHECK: address the decryption is attested to
+    pub allowed_address: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifySignature<'info> {
+    /// CHECK: Instructions sysvar
+    #[account(address = instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+#[error_code]
+pub enum MockIncoError {
+    #[msg("Ciphertext must be 16 little-endian bytes")]
+    InvalidCiphertext,
+    #[msg("Allowance account does not match handle and address")]
+    InvalidAllowance,
+    #[msg("Plaintext does not match handle")]
+    InvalidSignature,
+    #[msg("Address is not allowed to decrypt the handle")]
+    NotAllowed,
+}